@@ -1,12 +1,18 @@
-use rand::Rng;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 use crate::{
-    card::Card,
-    evaluator::Evaluator,
-    model::{Board, Deal, Hand, PlayerHand},
+    card::{Card, CardParseError},
+    deck::{Deck, DeckError},
+    evaluator::{EvaluationError, EVALUATOR},
+    live_hand::{LiveHand, Street},
+    model::{Board, Deal, Hand},
 };
 
-static CARDS: [&str; 52] = [
+pub(crate) static CARDS: [&str; 52] = [
     "Ac", "Ad", "Ah", "As", "2c", "2d", "2h", "2s", "3c", "3d", "3h", "3s", "4c", "4d", "4h", "4s",
     "5c", "5d", "5h", "5s", "6c", "6d", "6h", "6s", "7c", "7d", "7h", "7s", "8c", "8d", "8h", "8s",
     "9c", "9d", "9h", "9s", "Tc", "Td", "Th", "Ts", "Jc", "Jd", "Jh", "Js", "Qc", "Qd", "Qh", "Qs",
@@ -16,6 +22,20 @@ static CARDS: [&str; 52] = [
 // Define the interface
 pub trait CardShuffler {
     fn shuffle(&self) -> Vec<&'static str>;
+
+    /// Seed that replays the shuffles of this shuffler, if it has one.
+    fn seed(&self) -> Option<u64> {
+        None
+    }
+}
+
+pub(crate) fn shuffle_cards<R: Rng + ?Sized>(rng: &mut R) -> Vec<&'static str> {
+    let mut sample = CARDS;
+    for i in 0..CARDS.len() {
+        let rand: usize = rng.gen_range(0..=i);
+        sample.swap(i, rand);
+    }
+    sample.to_vec()
 }
 
 // Implement the interface for a struct
@@ -23,108 +43,196 @@ pub struct RandomCardShuffler;
 
 impl CardShuffler for RandomCardShuffler {
     fn shuffle(&self) -> Vec<&'static str> {
-        let mut rng = rand::thread_rng();
-        let mut sample = CARDS.clone();
-        for i in 0..CARDS.len() {
-            let rand: usize = rng.gen_range(0..=i);
-            sample.swap(i, rand);
+        shuffle_cards(&mut rand::thread_rng())
+    }
+}
+
+/// Shuffles with ChaCha8 seeded from a `u64`, so that the same seed always
+/// produces the same order of cards. The first shuffle uses the given seed
+/// and every later one a fresh seed drawn from it, so a dealer can be reused
+/// and each deal replayed with `SeededCardShuffler::new(deal.seed)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeededCardShuffler {
+    seeds: RefCell<ChaCha8Rng>,
+    next: Cell<u64>,
+    last: Cell<u64>,
+}
+
+impl SeededCardShuffler {
+    pub fn new(seed: u64) -> Self {
+        let mut seeds = ChaCha8Rng::seed_from_u64(seed);
+        // keep the seeds independent of the shuffle of the same seed
+        seeds.set_stream(1);
+        SeededCardShuffler {
+            seeds: RefCell::new(seeds),
+            next: Cell::new(seed),
+            last: Cell::new(seed),
         }
-        sample.to_vec()
+    }
+
+    /// A shuffler with a random seed, to be recorded for later replay.
+    pub fn from_entropy() -> Self {
+        SeededCardShuffler::new(rand::thread_rng().gen())
+    }
+}
+
+impl CardShuffler for SeededCardShuffler {
+    fn shuffle(&self) -> Vec<&'static str> {
+        let seed = self.next.replace(self.seeds.borrow_mut().gen());
+        self.last.set(seed);
+        shuffle_cards(&mut ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    /// The seed of the last shuffle, or of the first one before any.
+    fn seed(&self) -> Option<u64> {
+        Some(self.last.get())
     }
 }
 
-struct IndexGenerator {
-    index: usize,
+/// Shuffles with any `RngCore`, advancing it on every shuffle.
+pub struct RngCardShuffler<R: RngCore> {
+    rng: RefCell<R>,
 }
 
-impl IndexGenerator {
-    fn new() -> IndexGenerator {
-        IndexGenerator { index: 0 }
+impl<R: RngCore> RngCardShuffler<R> {
+    pub fn new(rng: R) -> Self {
+        RngCardShuffler { rng: RefCell::new(rng) }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.rng.into_inner()
     }
 }
 
-impl Iterator for IndexGenerator {
-    type Item = usize;
+impl<R: RngCore> CardShuffler for RngCardShuffler<R> {
+    fn shuffle(&self) -> Vec<&'static str> {
+        shuffle_cards(&mut *self.rng.borrow_mut())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DealError {
+    InvalidCard(CardParseError),
+    Evaluation(EvaluationError),
+    Deck(DeckError),
+    HandComplete,
+    NotEnoughPlayers(usize),
+    InvalidButton { button: usize, player_count: usize },
+}
+
+impl fmt::Display for DealError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DealError::InvalidCard(e) => write!(f, "invalid card in shuffled deck: {}", e),
+            DealError::Evaluation(e) => write!(f, "could not evaluate hand: {}", e),
+            DealError::Deck(e) => write!(f, "could not deal: {}", e),
+            DealError::HandComplete => write!(f, "the river has already been dealt"),
+            DealError::NotEnoughPlayers(n) => write!(f, "cannot deal to {} players, need at least 2", n),
+            DealError::InvalidButton {
+                button,
+                player_count,
+            } => write!(f, "button seat {} is not one of {} seats", button, player_count),
+        }
+    }
+}
+
+impl std::error::Error for DealError {}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let current_index = self.index;
-        self.index += 1;
-        Some(current_index)
+impl From<CardParseError> for DealError {
+    fn from(e: CardParseError) -> Self {
+        DealError::InvalidCard(e)
+    }
+}
+
+impl From<EvaluationError> for DealError {
+    fn from(e: EvaluationError) -> Self {
+        DealError::Evaluation(e)
+    }
+}
+
+impl From<DeckError> for DealError {
+    fn from(e: DeckError) -> Self {
+        DealError::Deck(e)
     }
 }
 
 pub trait Dealer {
-    fn deal(&self, player_count: usize) -> Deal;
+    fn deal(&self, player_count: usize) -> Result<Deal, DealError>;
 }
 
 pub struct GameDealer<S: CardShuffler> {
     shuffler: S,
+    button: usize,
 }
 
 impl<S: CardShuffler> GameDealer<S> {
-    #[allow(dead_code)]
+    /// A dealer with the button on seat 0.
     pub fn new(shuffler: S) -> Self {
-        GameDealer { shuffler }
+        GameDealer { shuffler, button: 0 }
+    }
+
+    /// Moves the button, the first card going to the seat on its left.
+    pub fn with_button(mut self, button: usize) -> Self {
+        self.button = button;
+        self
+    }
+
+    pub fn button(&self) -> usize {
+        self.button
+    }
+
+    /// Shuffles and deals the hole cards of a hand to be played street by
+    /// street.
+    pub fn start_hand(&self, player_count: usize) -> Result<LiveHand, DealError> {
+        let cards = self
+            .shuffler
+            .shuffle()
+            .into_iter()
+            .map(Card::new)
+            .collect::<Result<Vec<Card>, _>>()?;
+        LiveHand::new(Deck::from_cards(cards), player_count, self.button, self.shuffler.seed())
     }
 }
 
 impl<S: CardShuffler> Dealer for GameDealer<S> {
-    fn deal(&self, player_count: usize) -> Deal {
-        let evaluator = Evaluator::new();
-        let cards = self.shuffler.shuffle();
-        let mut hands: Vec<PlayerHand> = Vec::new();
-        let mut nextn = IndexGenerator::new();
-        let players: Vec<_> = (0..player_count).collect();
-        let pl = players.len();
-        for _ in players {
-            let i = nextn.next().unwrap();
-            let player_hand: Vec<String> = vec![cards[i].to_string(), cards[i + pl].to_string()];
-            let scores: Vec<u32> = player_hand.iter().map(|card| Card::new(card).0).collect();
-            hands.push(PlayerHand {
-                hand: player_hand,
-                score: scores,
-            });
+    fn deal(&self, player_count: usize) -> Result<Deal, DealError> {
+        let evaluator = &EVALUATOR;
+        let mut live_hand = self.start_hand(player_count)?;
+        while live_hand.street() != Street::River {
+            live_hand.advance()?;
         }
-        let flop = vec![
-            cards[nextn.next().unwrap() + pl].to_string(),
-            cards[nextn.next().unwrap() + pl].to_string(),
-            cards[nextn.next().unwrap() + pl].to_string(),
-        ];
-        let flop_score: Vec<u32> = flop.iter().map(|card| Card::new(card.as_str()).0).collect();
-        let turn = cards[nextn.next().unwrap() + pl];
-        let turn_score = Card::new(turn).0;
-        let river = cards[nextn.next().unwrap() + pl];
-        let river_score = Card::new(river).0;
+        let board_cards = live_hand.board();
         let mut player_hands: Vec<Hand> = Vec::new();
-        for hand in hands {
-            let mut combined_score = flop_score.clone();
-            combined_score.push(turn_score);
-            combined_score.push(river_score);
-            let hand_score = evaluator.evaluate(hand.score, combined_score);
-            let score = evaluator.get_rank_class(hand_score);
-            let percentage = 1.0 - evaluator.get_five_card_rank_percentage(hand_score);
-            let description = evaluator.class_to_string(score.unwrap());
+        for hole_cards in live_hand.hole_cards() {
+            let evaluation = evaluator.evaluate_best_five(hole_cards, board_cards)?;
+            let percentage = 1.0 - evaluator.get_five_card_rank_percentage(evaluation.rank);
+            let description = evaluation.description();
             player_hands.push(Hand {
-                cards: hand.hand.clone(),
+                cards: hole_cards.iter().map(Card::to_string).collect(),
                 score: percentage,
                 description,
+                rank: evaluation.rank,
             });
         }
         let board = Board {
-            flop: flop.clone(),
-            turn: turn.to_string(),
-            river: river.to_string(),
+            flop: board_cards[..3].iter().map(Card::to_string).collect(),
+            turn: board_cards[3].to_string(),
+            river: board_cards[4].to_string(),
         };
-        Deal {
+        Ok(Deal {
             board,
+            standings: Deal::standings_of(&player_hands),
             hands: player_hands,
-        }
+            button: self.button,
+            seed: self.shuffler.seed(),
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::hand_rank::HandRank;
 
     struct MockCardShuffler;
 
@@ -138,31 +246,138 @@ mod tests {
     fn test_deal() {
         let expected_deal = Deal {
             board: Board {
-                flop: vec!["2h".to_string(), "2s".to_string(), "3c".to_string()],
-                turn: "3d".to_string(),
-                river: "3h".to_string(),
+                flop: vec!["2s".to_string(), "3c".to_string(), "3d".to_string()],
+                turn: "3s".to_string(),
+                river: "4d".to_string(),
             },
             hands: vec![
+                Hand {
+                    cards: vec!["Ah".to_string(), "2d".to_string()],
+                    score: 0.9584561779683731,
+                    description: "Full House, Threes full of Twos".to_string(),
+                    rank: HandRank::new(310).unwrap(),
+                },
                 Hand {
                     cards: vec!["Ac".to_string(), "As".to_string()],
                     score: 0.9599303135888502,
-                    description: "Full House".to_string(),
+                    description: "Full House, Threes full of Aces".to_string(),
+                    rank: HandRank::new(299).unwrap(),
                 },
                 Hand {
                     cards: vec!["Ad".to_string(), "2c".to_string()],
                     score: 0.9584561779683731,
-                    description: "Full House".to_string(),
-                },
-                Hand {
-                    cards: vec!["Ah".to_string(), "2d".to_string()],
-                    score: 0.9584561779683731,
-                    description: "Full House".to_string(),
+                    description: "Full House, Threes full of Twos".to_string(),
+                    rank: HandRank::new(310).unwrap(),
                 },
             ],
+            standings: vec![vec![1], vec![0, 2]],
+            button: 0,
+            seed: None,
         };
         let mock_shuffler = MockCardShuffler;
         let dealer = GameDealer::new(mock_shuffler);
-        let deal = dealer.deal(3);
+        let deal = dealer.deal(3).unwrap();
         assert!(deal == expected_deal);
+        assert_eq!(deal.winners(), &[1]);
+        assert!(!deal.is_split_pot());
+    }
+
+    #[test]
+    fn test_deal_button() {
+        let deal = GameDealer::new(MockCardShuffler).with_button(1).deal(3).unwrap();
+        assert_eq!(deal.button, 1);
+        // first card to seat 2, then seats 0 and 1
+        assert_eq!(deal.hands[2].cards, vec!["Ac", "As"]);
+        assert_eq!(deal.hands[0].cards, vec!["Ad", "2c"]);
+        assert_eq!(deal.hands[1].cards, vec!["Ah", "2d"]);
+        assert_eq!(deal.board.flop, vec!["2s", "3c", "3d"]);
+        assert_eq!(
+            GameDealer::new(MockCardShuffler).with_button(3).deal(3),
+            Err(DealError::InvalidButton {
+                button: 3,
+                player_count: 3
+            })
+        );
+    }
+
+    struct BoardCardShuffler;
+
+    impl CardShuffler for BoardCardShuffler {
+        fn shuffle(&self) -> Vec<&'static str> {
+            let dealt = ["2c", "3d", "7h", "8s", "4c", "Ts", "Js", "Qs", "5c", "Ks", "6c", "As"];
+            let rest = CARDS.iter().filter(|card| !dealt.contains(card));
+            dealt.iter().chain(rest).copied().collect()
+        }
+    }
+
+    #[test]
+    fn test_deal_split_pot() {
+        let deal = GameDealer::new(BoardCardShuffler).deal(2).unwrap();
+        assert_eq!(deal.board.flop, vec!["Ts", "Js", "Qs"]);
+        assert_eq!(deal.hands[0].rank, HandRank::BEST);
+        assert_eq!(deal.standings, vec![vec![0, 1]]);
+        assert_eq!(deal.winners(), &[0, 1]);
+        assert!(deal.is_split_pot());
+    }
+
+    #[test]
+    fn test_seeded_deal_replays() {
+        let deal = GameDealer::new(SeededCardShuffler::new(42)).deal(6).unwrap();
+        assert_eq!(deal.seed, Some(42));
+        let replayed = GameDealer::new(SeededCardShuffler::new(deal.seed.unwrap())).deal(6).unwrap();
+        assert_eq!(deal, replayed);
+        let other = SeededCardShuffler::new(43).shuffle();
+        assert_ne!(SeededCardShuffler::new(42).shuffle(), other);
+
+        // a reused dealer deals new hands that still replay from their seed
+        let dealer = GameDealer::new(SeededCardShuffler::from_entropy());
+        let (first, second) = (dealer.deal(6).unwrap(), dealer.deal(6).unwrap());
+        assert_ne!(first.seed, second.seed);
+        assert_ne!(first.hands, second.hands);
+        let replayed = GameDealer::new(SeededCardShuffler::new(second.seed.unwrap())).deal(6).unwrap();
+        assert_eq!(replayed, second);
+    }
+
+    #[test]
+    fn test_seeded_shuffle_known_answer() {
+        // stored seeds must keep replaying the same cards across dependency upgrades
+        assert_eq!(
+            SeededCardShuffler::new(42).shuffle().join(" "),
+            "Ah Qd 6d Ad 6h Th 3h 4s Qs 3d As 7h Ac 5s Kc 2d Kh 5c 7d Jd Ks 9s 2s 8h Kd 8c \
+             8s Js 9d 7c 9h 3c Jc 4c 2c 4h 4d 6s Td Qc 7s 8d 9c Ts 5d Jh 3s Tc 6c Qh 2h 5h"
+        );
+    }
+
+    #[test]
+    fn test_rng_shuffler() {
+        let shuffler = RngCardShuffler::new(ChaCha8Rng::seed_from_u64(7));
+        let first = shuffler.shuffle();
+        assert_ne!(first, shuffler.shuffle());
+        assert_eq!(first, SeededCardShuffler::new(7).shuffle());
+        assert_eq!(shuffler.seed(), None);
+        let mut sorted = first.clone();
+        sorted.sort();
+        let mut cards = CARDS.to_vec();
+        cards.sort();
+        assert_eq!(sorted, cards);
+    }
+
+    struct InvalidCardShuffler;
+
+    impl CardShuffler for InvalidCardShuffler {
+        fn shuffle(&self) -> Vec<&'static str> {
+            let mut cards = CARDS.to_vec();
+            cards[0] = "1c";
+            cards
+        }
+    }
+
+    #[test]
+    fn test_deal_invalid_card() {
+        let dealer = GameDealer::new(InvalidCardShuffler);
+        assert_eq!(
+            dealer.deal(3),
+            Err(DealError::InvalidCard(CardParseError::InvalidRank('1')))
+        );
     }
 }
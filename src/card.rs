@@ -1,7 +1,176 @@
-#[derive(Debug, PartialEq)]
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardParseError {
+    Empty,
+    InvalidRank(char),
+    MissingSuit,
+    InvalidSuit(char),
+    TrailingCharacters(String),
+}
+
+impl fmt::Display for CardParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardParseError::Empty => write!(f, "empty card string"),
+            CardParseError::InvalidRank(c) => write!(f, "invalid card rank '{}'", c),
+            CardParseError::MissingSuit => write!(f, "missing card suit"),
+            CardParseError::InvalidSuit(c) => write!(f, "invalid card suit '{}'", c),
+            CardParseError::TrailingCharacters(rest) => {
+                write!(f, "unexpected trailing characters \"{}\"", rest)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CardParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rank {
+    Two = 0,
+    Three = 1,
+    Four = 2,
+    Five = 3,
+    Six = 4,
+    Seven = 5,
+    Eight = 6,
+    Nine = 7,
+    Ten = 8,
+    Jack = 9,
+    Queen = 10,
+    King = 11,
+    Ace = 12,
+}
+
+impl Rank {
+    pub const ALL: [Rank; 13] = [
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+        Rank::Ace,
+    ];
+
+    /// Iterates from deuce up to ace.
+    pub fn iter() -> impl DoubleEndedIterator<Item = Rank> {
+        Rank::ALL.into_iter()
+    }
+
+    pub fn from_int(rank_int: u32) -> Option<Rank> {
+        Rank::ALL.get(rank_int as usize).copied()
+    }
+
+    pub fn from_char(c: char) -> Option<Rank> {
+        let c = c.to_ascii_uppercase();
+        Rank::iter().find(|r| r.to_char() == c)
+    }
+
+    pub fn to_char(self) -> char {
+        Card::STR_RANKS[self as usize]
+    }
+
+    pub fn prime(self) -> u32 {
+        Card::PRIMES[self as usize]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Rank::Two => "Two",
+            Rank::Three => "Three",
+            Rank::Four => "Four",
+            Rank::Five => "Five",
+            Rank::Six => "Six",
+            Rank::Seven => "Seven",
+            Rank::Eight => "Eight",
+            Rank::Nine => "Nine",
+            Rank::Ten => "Ten",
+            Rank::Jack => "Jack",
+            Rank::Queen => "Queen",
+            Rank::King => "King",
+            Rank::Ace => "Ace",
+        }
+    }
+
+    pub fn plural_name(self) -> &'static str {
+        match self {
+            Rank::Two => "Twos",
+            Rank::Three => "Threes",
+            Rank::Four => "Fours",
+            Rank::Five => "Fives",
+            Rank::Six => "Sixes",
+            Rank::Seven => "Sevens",
+            Rank::Eight => "Eights",
+            Rank::Nine => "Nines",
+            Rank::Ten => "Tens",
+            Rank::Jack => "Jacks",
+            Rank::Queen => "Queens",
+            Rank::King => "Kings",
+            Rank::Ace => "Aces",
+        }
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+/// Suits carry the same bit values used in the cdhs nibble of a card integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Suit {
+    Spades = 1,
+    Hearts = 2,
+    Diamonds = 4,
+    Clubs = 8,
+}
+
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+    pub fn iter() -> impl DoubleEndedIterator<Item = Suit> {
+        Suit::ALL.into_iter()
+    }
+
+    pub fn from_int(suit_int: u32) -> Option<Suit> {
+        Suit::iter().find(|s| *s as u32 == suit_int)
+    }
+
+    pub fn from_char(c: char) -> Option<Suit> {
+        let c = c.to_ascii_lowercase();
+        Suit::iter().find(|s| s.to_char() == c)
+    }
+
+    pub fn to_char(self) -> char {
+        Card::INT_SUIT_TO_CHAR_SUIT[self as usize]
+    }
+
+    /// Position of the suit in `Suit::ALL`, from 0 to 3.
+    pub fn index(self) -> usize {
+        (self as u32).trailing_zeros() as usize
+    }
+}
+
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Card(pub u32);
 
 impl Card {
+    pub const STR_RANKS: [char; 13] = ['2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A'];
     pub const INT_RANKS: [u32; 13] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
     pub const PRIMES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
     pub const CHAR_RANK_TO_INT_RANK: [(char, u32); 13] = [
@@ -25,16 +194,34 @@ impl Card {
         ('d', 4), // diamonds
         ('c', 8), // clubs
     ];
+    pub const INT_SUIT_TO_CHAR_SUIT: [char; 9] = ['x', 's', 'h', 'x', 'd', 'x', 'x', 'x', 'c'];
+
+    /// Parses a card such as "Ah", "Td" or "10c". Ranks and suits are case-insensitive.
+    pub fn new(string: &str) -> Result<Self, CardParseError> {
+        let mut chars = string.chars();
+        let rank = match chars.next() {
+            Some('1') if chars.as_str().starts_with('0') => {
+                chars.next();
+                Rank::Ten
+            }
+            Some(c) => Rank::from_char(c).ok_or(CardParseError::InvalidRank(c))?,
+            None => return Err(CardParseError::Empty),
+        };
+        let suit_char = chars.next().ok_or(CardParseError::MissingSuit)?;
+        let suit = Suit::from_char(suit_char).ok_or(CardParseError::InvalidSuit(suit_char))?;
+        if !chars.as_str().is_empty() {
+            return Err(CardParseError::TrailingCharacters(chars.as_str().to_string()));
+        }
 
-    pub fn new(string: &str) -> Self {
-        let rank_char = string.chars().next().unwrap();
-        let suit_char = string.chars().nth(1).unwrap();
-        let rank_int = Card::CHAR_RANK_TO_INT_RANK.iter().find(|&&(r, _)| r == rank_char).unwrap().1;
-        let suit_int = Card::CHAR_SUIT_TO_INT_SUIT.iter().find(|&&(s, _)| s == suit_char).unwrap().1;
+        Ok(Card::from_parts(rank, suit))
+    }
+
+    pub const fn from_parts(rank: Rank, suit: Suit) -> Self {
+        let rank_int = rank as u32;
         let rank_prime = Card::PRIMES[rank_int as usize];
 
         let bitrank = 1 << rank_int << 16;
-        let suit = suit_int << 12;
+        let suit = (suit as u32) << 12;
         let rank = rank_int << 8;
 
         let card_int = bitrank | suit | rank | rank_prime;
@@ -42,6 +229,60 @@ impl Card {
         Card(card_int)
     }
 
+    pub fn rank(&self) -> Rank {
+        Rank::ALL[Card::get_rank_int(self.0) as usize]
+    }
+
+    pub fn suit(&self) -> Suit {
+        Suit::from_int(Card::get_suit_int(self.0)).unwrap()
+    }
+
+    /// A dense index from 0 to 51, ordered by rank and then suit.
+    pub fn index(&self) -> usize {
+        self.rank() as usize * 4 + self.suit().index()
+    }
+
+    /// Checks that an integer is one of the 52 card encodings.
+    pub fn from_int(card_int: u32) -> Option<Self> {
+        let rank = Rank::from_int(Card::get_rank_int(card_int))?;
+        let suit = Suit::from_int(Card::get_suit_int(card_int))?;
+        let card = Card::from_parts(rank, suit);
+        (card.0 == card_int).then_some(card)
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        let rank = Rank::from_int((index / 4) as u32)?;
+        Some(Card::from_parts(rank, Suit::ALL[index % 4]))
+    }
+
+    /// Converts a card integer back to its two character string, e.g. "Ah".
+    pub fn int_to_str(card_int: u32) -> String {
+        let rank_char = Card::STR_RANKS[Card::get_rank_int(card_int) as usize];
+        let suit_char = Card::INT_SUIT_TO_CHAR_SUIT[Card::get_suit_int(card_int) as usize];
+        format!("{}{}", rank_char, suit_char)
+    }
+
+    pub fn get_rank_int(card_int: u32) -> u32 {
+        (card_int >> 8) & 0xF
+    }
+
+    pub fn get_suit_int(card_int: u32) -> u32 {
+        (card_int >> 12) & 0xF
+    }
+
+    pub fn get_bitrank_int(card_int: u32) -> u32 {
+        (card_int >> 16) & 0x1FFF
+    }
+
+    pub fn get_prime(card_int: u32) -> u32 {
+        card_int & 0x3F
+    }
+
+    /// Converts a batch of card strings to their integer representation.
+    pub fn hand_to_binary(card_strs: &[&str]) -> Result<Vec<u32>, CardParseError> {
+        card_strs.iter().map(|s| Card::new(s).map(|c| c.0)).collect()
+    }
+
     pub fn prime_product_from_rankbits(rankbits: u32) -> u32 {
         let mut product = 1;
         for i in Card::INT_RANKS.iter() {
@@ -61,16 +302,122 @@ impl Card {
     }
 }
 
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&Card::int_to_str(self.0))
+    }
+}
+
+impl FromStr for Card {
+    type Err = CardParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Card::new(s)
+    }
+}
+
+/// Cards serialize as their two character string, e.g. "Ah".
+#[cfg(feature = "serde")]
+impl serde::Serialize for Card {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Card {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Card::new(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
      #[test]
     fn test_new() {
-        assert_eq!(Card::new("2s").0, 69634);
-        assert_eq!(Card::new("3h").0, 139523);
-        assert_eq!(Card::new("4d").0, 279045);
-        assert_eq!(Card::new("Ac").0, 268471337);
+        assert_eq!(Card::new("2s").unwrap().0, 69634);
+        assert_eq!(Card::new("3h").unwrap().0, 139523);
+        assert_eq!(Card::new("4d").unwrap().0, 279045);
+        assert_eq!(Card::new("Ac").unwrap().0, 268471337);
+    }
+
+    #[test]
+    fn test_new_variants() {
+        assert_eq!(Card::new("10h"), Card::new("Th"));
+        assert_eq!(Card::new("as"), Card::new("As"));
+        assert_eq!(Card::new("KD"), Card::new("Kd"));
+        assert_eq!("Qc".parse::<Card>(), Card::new("Qc"));
+    }
+
+    #[test]
+    fn test_new_errors() {
+        assert_eq!(Card::new(""), Err(CardParseError::Empty));
+        assert_eq!(Card::new("Xh"), Err(CardParseError::InvalidRank('X')));
+        assert_eq!(Card::new("1h"), Err(CardParseError::InvalidRank('1')));
+        assert_eq!(Card::new("A"), Err(CardParseError::MissingSuit));
+        assert_eq!(Card::new("Ax"), Err(CardParseError::InvalidSuit('x')));
+        assert_eq!(
+            Card::new("Ahh"),
+            Err(CardParseError::TrailingCharacters("h".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_rank_and_suit() {
+        assert_eq!(Rank::iter().count(), 13);
+        assert!(Rank::Ace > Rank::King);
+        assert_eq!(Rank::from_char('t'), Some(Rank::Ten));
+        assert_eq!(Rank::from_int(13), None);
+        assert_eq!(Suit::from_char('H'), Some(Suit::Hearts));
+        assert_eq!(Suit::from_int(3), None);
+        assert_eq!(Card::from_index(52), None);
+        for rank in Rank::iter() {
+            for suit in Suit::iter() {
+                let card = Card::from_parts(rank, suit);
+                assert_eq!(card.rank(), rank);
+                assert_eq!(card.suit(), suit);
+                assert_eq!(card, Card::new(&format!("{}{}", rank, suit)).unwrap());
+                assert_eq!(Card::from_index(card.index()), Some(card));
+            }
+        }
+    }
+
+    #[test]
+    fn test_int_to_str() {
+        assert_eq!(Card::int_to_str(69634), "2s");
+        assert_eq!(Card::int_to_str(139523), "3h");
+        assert_eq!(Card::int_to_str(279045), "4d");
+        assert_eq!(Card::int_to_str(268471337), "Ac");
+        for &rank in Card::STR_RANKS.iter() {
+            for &(suit, _) in Card::CHAR_SUIT_TO_INT_SUIT.iter() {
+                let s = format!("{}{}", rank, suit);
+                assert_eq!(Card::new(&s).unwrap().to_string(), s);
+            }
+        }
+    }
+
+    #[test]
+    fn test_int_accessors() {
+        let card = Card::new("Kd").unwrap().0;
+        assert_eq!(Card::get_rank_int(card), 11);
+        assert_eq!(Card::get_suit_int(card), 4);
+        assert_eq!(Card::get_bitrank_int(card), 1 << 11);
+        assert_eq!(Card::get_prime(card), 37);
+    }
+
+    #[test]
+    fn test_hand_to_binary() {
+        assert_eq!(
+            Card::hand_to_binary(&["2s", "3h", "4d", "Ac"]),
+            Ok(vec![69634, 139523, 279045, 268471337])
+        );
+        assert_eq!(
+            Card::hand_to_binary(&["2s", "3x"]),
+            Err(CardParseError::InvalidSuit('x'))
+        );
     }
 
     #[test]
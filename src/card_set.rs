@@ -0,0 +1,267 @@
+use std::fmt;
+use std::ops::{BitAnd, BitOr, Not, Sub};
+use std::str::FromStr;
+
+use crate::card::{Card, CardParseError};
+
+/// A set of cards stored as a 52 bit mask, one bit per `Card::index`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CardSet(u64);
+
+impl CardSet {
+    const FULL_MASK: u64 = (1 << 52) - 1;
+
+    pub const fn new() -> Self {
+        CardSet(0)
+    }
+
+    pub const fn full() -> Self {
+        CardSet(CardSet::FULL_MASK)
+    }
+
+    /// Builds a set from a raw mask, ignoring bits above 51.
+    pub const fn from_mask(mask: u64) -> Self {
+        CardSet(mask & CardSet::FULL_MASK)
+    }
+
+    pub const fn mask(&self) -> u64 {
+        self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, card: Card) -> bool {
+        self.0 & (1 << card.index()) != 0
+    }
+
+    /// Adds a card, returning whether it was newly inserted.
+    pub fn insert(&mut self, card: Card) -> bool {
+        let present = self.contains(card);
+        self.0 |= 1 << card.index();
+        !present
+    }
+
+    /// Removes a card, returning whether it was present.
+    pub fn remove(&mut self, card: Card) -> bool {
+        let present = self.contains(card);
+        self.0 &= !(1 << card.index());
+        present
+    }
+
+    pub const fn union(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+
+    pub const fn intersection(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+
+    pub const fn difference(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 & !other.0)
+    }
+
+    pub const fn is_disjoint(&self, other: CardSet) -> bool {
+        self.0 & other.0 == 0
+    }
+
+    pub const fn is_subset(&self, other: CardSet) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    /// Iterates the cards in ascending `Card::index` order.
+    pub fn iter(&self) -> CardSetIter {
+        CardSetIter(self.0)
+    }
+
+    pub fn to_vec(&self) -> Vec<Card> {
+        self.iter().collect()
+    }
+}
+
+impl From<&[Card]> for CardSet {
+    fn from(cards: &[Card]) -> Self {
+        cards.iter().copied().collect()
+    }
+}
+
+impl From<CardSet> for Vec<Card> {
+    fn from(set: CardSet) -> Self {
+        set.to_vec()
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(iter: I) -> Self {
+        let mut set = CardSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<Card> for CardSet {
+    fn extend<I: IntoIterator<Item = Card>>(&mut self, iter: I) {
+        for card in iter {
+            self.insert(card);
+        }
+    }
+}
+
+pub struct CardSetIter(u64);
+
+impl Iterator for CardSetIter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Card::from_index(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CardSetIter {}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, rhs: CardSet) -> CardSet {
+        self.union(rhs)
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = CardSet;
+
+    fn bitand(self, rhs: CardSet) -> CardSet {
+        self.intersection(rhs)
+    }
+}
+
+impl Sub for CardSet {
+    type Output = CardSet;
+
+    fn sub(self, rhs: CardSet) -> CardSet {
+        self.difference(rhs)
+    }
+}
+
+impl Not for CardSet {
+    type Output = CardSet;
+
+    fn not(self) -> CardSet {
+        CardSet::full().difference(self)
+    }
+}
+
+/// The compact form concatenates the cards, e.g. "2sAhKd".
+impl fmt::Display for CardSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for card in self.iter() {
+            write!(f, "{}", card)?;
+        }
+        Ok(())
+    }
+}
+
+/// Accepts the compact form as well as cards separated by spaces or commas,
+/// e.g. "AhKd", "Ah Kd" or "Ah,10d".
+impl FromStr for CardSet {
+    type Err = CardParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = CardSet::new();
+        for card in parse_cards(s)? {
+            set.insert(card);
+        }
+        Ok(set)
+    }
+}
+
+/// Splits compact ("AhKd", "10h9h") or separated ("Ah Kd", "Ah,Kd") cards, in
+/// order and keeping duplicates.
+pub(crate) fn parse_cards(s: &str) -> Result<Vec<Card>, CardParseError> {
+    let mut cards = Vec::new();
+    for token in s.split(|c: char| c.is_whitespace() || c == ',') {
+        let mut rest = token;
+        while !rest.is_empty() {
+            let len = if rest.starts_with("10") { 3 } else { 2 };
+            let split = rest
+                .char_indices()
+                .nth(len)
+                .map_or(rest.len(), |(i, _)| i);
+            cards.push(Card::new(&rest[..split])?);
+            rest = &rest[split..];
+        }
+    }
+    Ok(cards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(s: &str) -> Card {
+        Card::new(s).unwrap()
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set = CardSet::new();
+        assert!(set.insert(card("Ah")));
+        assert!(!set.insert(card("Ah")));
+        assert!(set.insert(card("2s")));
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(card("Ah")));
+        assert!(set.remove(card("Ah")));
+        assert!(!set.remove(card("Ah")));
+        assert_eq!(set.to_vec(), vec![card("2s")]);
+        assert_eq!(CardSet::full().len(), 52);
+        assert!((!CardSet::full()).is_empty());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: CardSet = "AhKhQh".parse().unwrap();
+        let b: CardSet = "Kh Qh Jh".parse().unwrap();
+        assert_eq!(a | b, "AhKhQhJh".parse().unwrap());
+        assert_eq!(a & b, "KhQh".parse().unwrap());
+        assert_eq!(a - b, "Ah".parse().unwrap());
+        assert!((a & b).is_subset(a));
+        assert!((a - b).is_disjoint(b));
+    }
+
+    #[test]
+    fn test_text_form() {
+        let set: CardSet = "Ah, 10d 2s".parse().unwrap();
+        assert_eq!(set.to_string(), "2sTdAh");
+        assert_eq!(set.to_string().parse::<CardSet>(), Ok(set));
+        assert_eq!(
+            "AhK".parse::<CardSet>(),
+            Err(CardParseError::MissingSuit)
+        );
+        let cards = [card("Ah"), card("2s")];
+        assert_eq!(CardSet::from(&cards[..]), "2sAh".parse().unwrap());
+    }
+}
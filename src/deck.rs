@@ -0,0 +1,219 @@
+use std::fmt;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::card::{Card, Rank, Suit};
+use crate::card_set::CardSet;
+use crate::pretty::{PrettyCardsExt, PrettyStyle};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeckError {
+    NotEnoughCards { requested: usize, remaining: usize },
+    CardNotInDeck(Card),
+}
+
+impl fmt::Display for DeckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeckError::NotEnoughCards {
+                requested,
+                remaining,
+            } => write!(
+                f,
+                "cannot draw {} cards, only {} remaining",
+                requested, remaining
+            ),
+            DeckError::CardNotInDeck(card) => write!(f, "card {} is not in the deck", card),
+        }
+    }
+}
+
+impl std::error::Error for DeckError {}
+
+/// A deck of cards where index 0 is the top of the deck.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Deck {
+    cards: Vec<Card>,
+    burned: Vec<Card>,
+}
+
+impl Deck {
+    /// A full, unshuffled deck.
+    pub fn new() -> Self {
+        Deck {
+            cards: Deck::full_deck(),
+            burned: Vec::new(),
+        }
+    }
+
+    /// A deck in the given order, `cards[0]` being the top card.
+    pub fn from_cards(cards: Vec<Card>) -> Self {
+        Deck {
+            cards,
+            burned: Vec::new(),
+        }
+    }
+
+    /// A full deck shuffled with the thread rng.
+    pub fn shuffled() -> Self {
+        let mut deck = Deck::new();
+        deck.shuffle();
+        deck
+    }
+
+    /// The standard 52 card deck, ordered by rank and then suit.
+    pub fn full_deck() -> Vec<Card> {
+        Rank::iter()
+            .flat_map(|rank| Suit::iter().map(move |suit| Card::from_parts(rank, suit)))
+            .collect()
+    }
+
+    pub fn shuffle(&mut self) {
+        self.shuffle_with(&mut rand::thread_rng());
+    }
+
+    /// Shuffles the remaining cards with the given rng, e.g. a seeded one.
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.cards.shuffle(rng);
+    }
+
+    pub fn draw(&mut self, n: usize) -> Result<Vec<Card>, DeckError> {
+        if n > self.cards.len() {
+            return Err(DeckError::NotEnoughCards {
+                requested: n,
+                remaining: self.cards.len(),
+            });
+        }
+        Ok(self.cards.drain(..n).collect())
+    }
+
+    pub fn draw_one(&mut self) -> Result<Card, DeckError> {
+        Ok(self.draw(1)?[0])
+    }
+
+    /// Discards the top card, keeping track of it in `burned`.
+    pub fn burn(&mut self) -> Result<Card, DeckError> {
+        let card = self.draw_one()?;
+        self.burned.push(card);
+        Ok(card)
+    }
+
+    /// Removes a known dead card from wherever it sits in the deck.
+    pub fn remove(&mut self, card: Card) -> Result<(), DeckError> {
+        match self.cards.iter().position(|&c| c == card) {
+            Some(i) => {
+                self.cards.remove(i);
+                Ok(())
+            }
+            None => Err(DeckError::CardNotInDeck(card)),
+        }
+    }
+
+    pub fn remove_all(&mut self, cards: &[Card]) -> Result<(), DeckError> {
+        if let Some(&missing) = cards.iter().find(|&&c| !self.contains(c)) {
+            return Err(DeckError::CardNotInDeck(missing));
+        }
+        self.cards.retain(|c| !cards.contains(c));
+        Ok(())
+    }
+
+    /// Removes every card of the set that is still in the deck.
+    pub fn remove_set(&mut self, cards: CardSet) {
+        self.cards.retain(|&c| !cards.contains(c));
+    }
+
+    pub fn contains(&self, card: Card) -> bool {
+        self.cards.contains(&card)
+    }
+
+    pub fn remaining(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn remaining_set(&self) -> CardSet {
+        self.cards.iter().copied().collect()
+    }
+
+    pub fn burned(&self) -> &[Card] {
+        &self.burned
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Restores the full, unshuffled deck and forgets any burned cards.
+    pub fn reset(&mut self) {
+        self.cards = Deck::full_deck();
+        self.burned.clear();
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Deck::new()
+    }
+}
+
+impl fmt::Display for Deck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.cards.pretty(PrettyStyle::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_deck() {
+        let deck = Deck::new();
+        assert_eq!(deck.len(), 52);
+        assert_eq!(deck.remaining()[0], Card::new("2s").unwrap());
+        assert_eq!(deck.remaining()[51], Card::new("Ac").unwrap());
+        let mut cards = deck.remaining().to_vec();
+        cards.sort_by_key(|c| c.0);
+        cards.dedup();
+        assert_eq!(cards.len(), 52);
+    }
+
+    #[test]
+    fn test_draw_and_burn() {
+        let mut deck = Deck::new();
+        let drawn = deck.draw(2).unwrap();
+        assert_eq!(drawn, vec![Card::new("2s").unwrap(), Card::new("2h").unwrap()]);
+        assert_eq!(deck.burn(), Ok(Card::new("2d").unwrap()));
+        assert_eq!(deck.burned(), &[Card::new("2d").unwrap()]);
+        assert_eq!(deck.len(), 49);
+        assert_eq!(
+            deck.draw(50),
+            Err(DeckError::NotEnoughCards {
+                requested: 50,
+                remaining: 49
+            })
+        );
+        deck.reset();
+        assert_eq!(deck, Deck::new());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut deck = Deck::shuffled();
+        let ace = Card::new("As").unwrap();
+        let king = Card::new("Kh").unwrap();
+        deck.remove_all(&[ace, king]).unwrap();
+        assert_eq!(deck.len(), 50);
+        assert!(!deck.contains(ace));
+        assert_eq!(deck.remove(ace), Err(DeckError::CardNotInDeck(ace)));
+        assert_eq!(deck.remove_all(&[king]), Err(DeckError::CardNotInDeck(king)));
+        deck.remove_set("AsKhQd".parse().unwrap());
+        assert_eq!(deck.len(), 49);
+        assert_eq!(deck.remaining_set(), !"AsKhQd".parse::<CardSet>().unwrap());
+    }
+}
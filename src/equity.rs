@@ -0,0 +1,448 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use itertools::Itertools;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::card::Card;
+use crate::card_set::CardSet;
+use crate::evaluator::EVALUATOR;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EquityError {
+    NotEnoughPlayers(usize),
+    InvalidBoardSize(usize),
+    DuplicateCard(Card),
+    NotEnoughCards { needed: usize, remaining: usize },
+    EmptyBudget,
+}
+
+impl fmt::Display for EquityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EquityError::NotEnoughPlayers(n) => write!(f, "equity needs at least 2 players, got {}", n),
+            EquityError::InvalidBoardSize(n) => write!(f, "board must have 0, 3, 4 or 5 cards, got {}", n),
+            EquityError::DuplicateCard(card) => write!(f, "card {} is used more than once", card),
+            EquityError::NotEnoughCards { needed, remaining } => {
+                write!(f, "the board needs {} more cards, only {} remaining", needed, remaining)
+            }
+            EquityError::EmptyBudget => write!(f, "the budget does not allow a single runout"),
+        }
+    }
+}
+
+impl std::error::Error for EquityError {}
+
+/// One player's share of the boards, each between 0.0 and 1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayerEquity {
+    pub win: f64,
+    pub tie: f64,
+    pub lose: f64,
+    /// Expected share of the pot, a tie between `n` players counting `1 / n`.
+    pub equity: f64,
+}
+
+/// Equity of every player, in the order the hands were given.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equity {
+    pub players: Vec<PlayerEquity>,
+    /// Number of boards the equity was computed over.
+    pub boards: u64,
+}
+
+/// Running win and tie counts over a number of boards.
+#[derive(Debug, Clone)]
+pub(crate) struct Tally {
+    wins: Vec<u64>,
+    ties: Vec<u64>,
+    shares: Vec<f64>,
+    squares: Vec<f64>,
+    boards: u64,
+}
+
+impl Tally {
+    pub(crate) fn new(player_count: usize) -> Self {
+        Tally {
+            wins: vec![0; player_count],
+            ties: vec![0; player_count],
+            shares: vec![0.0; player_count],
+            squares: vec![0.0; player_count],
+            boards: 0,
+        }
+    }
+
+    /// Records one board given each player's raw rank, lower being better.
+    pub(crate) fn record(&mut self, ranks: &[u32]) {
+        let best = *ranks.iter().min().unwrap();
+        let winners = ranks.iter().filter(|&&rank| rank == best).count();
+        for (player, &rank) in ranks.iter().enumerate() {
+            if rank != best {
+                continue;
+            }
+            if winners == 1 {
+                self.wins[player] += 1;
+            } else {
+                self.ties[player] += 1;
+            }
+            let share = 1.0 / winners as f64;
+            self.shares[player] += share;
+            self.squares[player] += share * share;
+        }
+        self.boards += 1;
+    }
+
+    /// Standard error of each player's mean share of the pot.
+    pub(crate) fn std_errors(&self) -> Vec<f64> {
+        if self.boards < 2 {
+            return vec![f64::INFINITY; self.shares.len()];
+        }
+        let n = self.boards as f64;
+        self.shares
+            .iter()
+            .zip(&self.squares)
+            .map(|(&sum, &squares)| {
+                let variance = (squares - sum * sum / n).max(0.0) / (n - 1.0);
+                (variance / n).sqrt()
+            })
+            .collect()
+    }
+
+    /// Share of the boards recorded so far, which must not be empty.
+    pub(crate) fn equity(&self) -> Equity {
+        let boards = self.boards as f64;
+        let players = (0..self.wins.len())
+            .map(|player| {
+                let win = self.wins[player] as f64 / boards;
+                let tie = self.ties[player] as f64 / boards;
+                PlayerEquity {
+                    win,
+                    tie,
+                    lose: 1.0 - win - tie,
+                    equity: self.shares[player] / boards,
+                }
+            })
+            .collect();
+        Equity {
+            players,
+            boards: self.boards,
+        }
+    }
+}
+
+/// Checks the inputs and returns the cards left to deal from.
+pub(crate) fn remaining_cards(hands: &[[Card; 2]], board: &[Card], dead: &[Card]) -> Result<Vec<Card>, EquityError> {
+    if hands.len() < 2 {
+        return Err(EquityError::NotEnoughPlayers(hands.len()));
+    }
+    if !matches!(board.len(), 0 | 3 | 4 | 5) {
+        return Err(EquityError::InvalidBoardSize(board.len()));
+    }
+    let mut used = CardSet::new();
+    for &card in hands.iter().flatten().chain(board).chain(dead) {
+        if !used.insert(card) {
+            return Err(EquityError::DuplicateCard(card));
+        }
+    }
+    let remaining = (!used).to_vec();
+    let needed = 5 - board.len();
+    if remaining.len() < needed {
+        return Err(EquityError::NotEnoughCards {
+            needed,
+            remaining: remaining.len(),
+        });
+    }
+    Ok(remaining)
+}
+
+/// Ranks of every hand on a complete board.
+pub(crate) fn rank_hands(hands: &[[Card; 2]], board: &[Card; 5], ranks: &mut [u32]) {
+    for (rank, hand) in ranks.iter_mut().zip(hands) {
+        let cards = [hand[0].0, hand[1].0, board[0].0, board[1].0, board[2].0, board[3].0, board[4].0];
+        // remaining_cards checked that the cards are distinct
+        *rank = EVALUATOR._seven(&cards).expect("seven distinct cards make a hand");
+    }
+}
+
+/// Exact equity of two or more hands, enumerating every way to complete the
+/// board from the cards that are not held, on the board or dead.
+///
+/// ```
+/// use deuces_rs::{equity, Card};
+///
+/// let cards = |s: &str| s.split(' ').map(|c| Card::new(c).unwrap()).collect::<Vec<_>>();
+/// let (ak, qq) = (cards("Ah Kh"), cards("Qs Qd"));
+/// let result = equity::enumerate(&[[ak[0], ak[1]], [qq[0], qq[1]]], &cards("2h 7h Qc 3d"), &[]).unwrap();
+/// assert_eq!(result.boards, 44);
+/// assert_eq!(result.players[0].win, 7.0 / 44.0);
+/// ```
+pub fn enumerate(hands: &[[Card; 2]], board: &[Card], dead: &[Card]) -> Result<Equity, EquityError> {
+    let remaining = remaining_cards(hands, board, dead)?;
+    let mut tally = Tally::new(hands.len());
+    let mut ranks = vec![0; hands.len()];
+    let mut full_board = [Card(0); 5];
+    full_board[..board.len()].copy_from_slice(board);
+    for runout in remaining.into_iter().combinations(5 - board.len()) {
+        full_board[board.len()..].copy_from_slice(&runout);
+        rank_hands(hands, &full_board, &mut ranks);
+        tally.record(&ranks);
+    }
+    Ok(tally.equity())
+}
+
+/// When a Monte Carlo run stops.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Budget {
+    Iterations(u64),
+    Time(Duration),
+    /// Until every player's standard error is at most `target`, giving up
+    /// after `max_iterations`.
+    StandardError { target: f64, max_iterations: u64 },
+}
+
+/// Estimated equity with the standard error of each player's equity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Estimate {
+    pub equity: Equity,
+    pub std_errors: Vec<f64>,
+    /// Seed that reproduces this estimate.
+    pub seed: u64,
+}
+
+impl Estimate {
+    /// z value of a 95% confidence interval.
+    pub const Z_95: f64 = 1.959963984540054;
+
+    /// Normal approximation interval of the player's equity, e.g. with
+    /// `Estimate::Z_95`, clamped to [0.0, 1.0].
+    pub fn confidence_interval(&self, player: usize, z: f64) -> (f64, f64) {
+        let equity = self.equity.players[player].equity;
+        let margin = z * self.std_errors[player];
+        ((equity - margin).max(0.0), (equity + margin).min(1.0))
+    }
+}
+
+/// Estimates equity from random runouts of the board, for when enumerating
+/// all of them is too slow.
+///
+/// ```
+/// use deuces_rs::equity::{Budget, MonteCarlo};
+/// use deuces_rs::Card;
+///
+/// let hand = |a: &str, b: &str| [Card::new(a).unwrap(), Card::new(b).unwrap()];
+/// let estimate = MonteCarlo::new(Budget::Iterations(2_000))
+///     .with_seed(7)
+///     .run(&[hand("Ah", "Kh"), hand("Qs", "Qd")], &[], &[])
+///     .unwrap();
+/// let (low, high) = estimate.confidence_interval(0, 4.0);
+/// assert!(low < 0.46 && 0.46 < high);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonteCarlo {
+    budget: Budget,
+    seed: Option<u64>,
+}
+
+impl MonteCarlo {
+    /// Runouts sampled between two checks of a time or error budget.
+    const BATCH: u64 = 1000;
+
+    pub fn new(budget: Budget) -> Self {
+        MonteCarlo { budget, seed: None }
+    }
+
+    /// Samples from a ChaCha8 rng seeded with `seed` for reproducible results.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Samples each runout with a partial Fisher-Yates shuffle of the
+    /// remaining cards, only drawing the cards the board is missing.
+    pub fn run(&self, hands: &[[Card; 2]], board: &[Card], dead: &[Card]) -> Result<Estimate, EquityError> {
+        let empty = match self.budget {
+            Budget::Iterations(n) | Budget::StandardError { max_iterations: n, .. } => n == 0,
+            Budget::Time(limit) => limit.is_zero(),
+        };
+        if empty {
+            return Err(EquityError::EmptyBudget);
+        }
+        let mut remaining = remaining_cards(hands, board, dead)?;
+        let seed = self.seed.unwrap_or_else(|| rand::thread_rng().gen());
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let start = Instant::now();
+
+        let mut tally = Tally::new(hands.len());
+        let mut ranks = vec![0; hands.len()];
+        let mut full_board = [Card(0); 5];
+        full_board[..board.len()].copy_from_slice(board);
+        // a time budget could run out before the first batch
+        while tally.boards == 0 || !self.is_done(&tally, start) {
+            let batch = match self.budget {
+                Budget::Iterations(n) => (n - tally.boards).min(Self::BATCH),
+                Budget::StandardError { max_iterations, .. } => (max_iterations - tally.boards).min(Self::BATCH),
+                Budget::Time(_) => Self::BATCH,
+            };
+            for _ in 0..batch {
+                // partial Fisher-Yates, the runout ends up at the front
+                for i in board.len()..5 {
+                    let j = rng.gen_range(i - board.len()..remaining.len());
+                    remaining.swap(i - board.len(), j);
+                    full_board[i] = remaining[i - board.len()];
+                }
+                rank_hands(hands, &full_board, &mut ranks);
+                tally.record(&ranks);
+            }
+        }
+
+        Ok(Estimate {
+            equity: tally.equity(),
+            std_errors: tally.std_errors(),
+            seed,
+        })
+    }
+
+    fn is_done(&self, tally: &Tally, start: Instant) -> bool {
+        match self.budget {
+            Budget::Iterations(n) => tally.boards >= n,
+            Budget::Time(limit) => start.elapsed() >= limit,
+            Budget::StandardError { target, max_iterations } => {
+                tally.boards >= max_iterations || tally.std_errors().iter().all(|&e| e <= target)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::Deck;
+
+    fn hand(s: &str) -> [Card; 2] {
+        let cards = cards(s);
+        [cards[0], cards[1]]
+    }
+
+    fn cards(s: &str) -> Vec<Card> {
+        s.split_whitespace().map(|c| Card::new(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_flop_equity() {
+        let result = enumerate(&[hand("Ah Kh"), hand("Qs Qd")], &cards("2h 7h Qc"), &[]).unwrap();
+        assert_eq!(result.boards, 990);
+        let (ak, qq) = (result.players[0], result.players[1]);
+        assert_eq!(ak.tie, 0.0);
+        assert_eq!(ak.win + qq.win, 1.0);
+        assert_eq!(ak.lose, qq.win);
+        // values checked against a deuces3 enumeration
+        assert_eq!(ak.win, 253.0 / 990.0);
+
+        // a dead heart takes away from the flush draw
+        let dead = enumerate(&[hand("Ah Kh"), hand("Qs Qd")], &cards("2h 7h Qc"), &cards("9h")).unwrap();
+        assert_eq!(dead.boards, 946);
+        assert_eq!(dead.players[0].equity, 219.0 / 946.0);
+    }
+
+    #[test]
+    fn test_split_and_multiway() {
+        let board = cards("Ts Js Qs Ks As");
+        let result = enumerate(&[hand("2c 3c"), hand("2d 3d"), hand("4h 5h")], &board, &[]).unwrap();
+        assert_eq!(result.boards, 1);
+        for player in result.players {
+            assert_eq!((player.win, player.tie, player.lose), (0.0, 1.0, 0.0));
+            assert!((player.equity - 1.0 / 3.0).abs() < 1e-12);
+        }
+
+        let result = enumerate(&[hand("Ah Ad"), hand("Kh Kd"), hand("7c 8c")], &cards("2s 9c Tc 4d"), &[]).unwrap();
+        let wins: Vec<_> = result.players.iter().map(|p| p.win * 42.0).collect();
+        assert_eq!(wins, vec![26.0, 1.0, 15.0]);
+        let total: f64 = result.players.iter().map(|p| p.equity).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_monte_carlo() {
+        let hands = [hand("Ah Kh"), hand("Qs Qd")];
+        let board = cards("2h 7h Qc");
+        let exact = enumerate(&hands, &board, &[]).unwrap().players[0].equity;
+
+        let estimate = MonteCarlo::new(Budget::Iterations(20_000))
+            .with_seed(1)
+            .run(&hands, &board, &[])
+            .unwrap();
+        assert_eq!(estimate.equity.boards, 20_000);
+        assert_eq!(estimate.seed, 1);
+        let (low, high) = estimate.confidence_interval(0, 4.0);
+        assert!(low < exact && exact < high, "{} not in ({}, {})", exact, low, high);
+
+        let replayed = MonteCarlo::new(Budget::Iterations(20_000)).with_seed(1).run(&hands, &board, &[]);
+        assert_eq!(replayed.unwrap(), estimate);
+        let other = MonteCarlo::new(Budget::Iterations(20_000)).with_seed(2).run(&hands, &board, &[]);
+        assert_ne!(other.unwrap(), estimate);
+    }
+
+    #[test]
+    fn test_monte_carlo_budgets() {
+        let hands = [hand("Ah Kh"), hand("Qs Qd"), hand("7c 8c")];
+        let budget = Budget::StandardError {
+            target: 0.01,
+            max_iterations: 1_000_000,
+        };
+        let estimate = MonteCarlo::new(budget).run(&hands, &[], &[]).unwrap();
+        assert!(estimate.std_errors.iter().all(|&e| e <= 0.01));
+        assert!(estimate.equity.boards < 1_000_000);
+
+        let capped = Budget::StandardError {
+            target: 0.0,
+            max_iterations: 1500,
+        };
+        assert_eq!(MonteCarlo::new(capped).run(&hands, &[], &[]).unwrap().equity.boards, 1500);
+
+        let timed = MonteCarlo::new(Budget::Time(Duration::from_millis(10)));
+        assert!(timed.run(&hands, &[], &[]).unwrap().equity.boards >= MonteCarlo::BATCH);
+
+        // a complete board has no variance
+        let river = MonteCarlo::new(Budget::Iterations(10)).run(&hands, &cards("2s 3s 4d 9h Jd"), &[]);
+        assert_eq!(river.unwrap().std_errors, vec![0.0; 3]);
+
+        for budget in [
+            Budget::Iterations(0),
+            Budget::Time(Duration::ZERO),
+            Budget::StandardError {
+                target: 0.01,
+                max_iterations: 0,
+            },
+        ] {
+            assert_eq!(MonteCarlo::new(budget).run(&hands, &[], &[]), Err(EquityError::EmptyBudget));
+        }
+        let instant = MonteCarlo::new(Budget::Time(Duration::from_nanos(1))).run(&hands, &[], &[]);
+        assert_eq!(instant.unwrap().equity.boards, MonteCarlo::BATCH);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert_eq!(enumerate(&[hand("Ah Kh")], &[], &[]), Err(EquityError::NotEnoughPlayers(1)));
+        assert_eq!(
+            enumerate(&[hand("Ah Kh"), hand("Qs Qd")], &cards("2c 3c"), &[]),
+            Err(EquityError::InvalidBoardSize(2))
+        );
+        assert_eq!(
+            enumerate(&[hand("Ah Kh"), hand("Qs Ah")], &[], &[]),
+            Err(EquityError::DuplicateCard(Card::new("Ah").unwrap()))
+        );
+
+        let hands = [hand("Ah Kh"), hand("Qs Qd")];
+        let dead: Vec<_> = Deck::full_deck().into_iter().filter(|c| !hands.iter().flatten().any(|h| h == c)).collect();
+        assert_eq!(
+            enumerate(&hands, &[], &dead[..44]),
+            Err(EquityError::NotEnoughCards { needed: 5, remaining: 4 })
+        );
+        assert_eq!(
+            MonteCarlo::new(Budget::Iterations(10)).run(&hands, &[], &dead[..44]),
+            Err(EquityError::NotEnoughCards { needed: 5, remaining: 4 })
+        );
+        assert_eq!(enumerate(&hands, &[], &dead[..43]).unwrap().boards, 1);
+    }
+}
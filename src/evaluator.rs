@@ -1,115 +1,583 @@
-use crate::{lookup::LookupTable, card::Card};
+use std::fmt;
+
+use crate::{
+    card::{Card, Rank},
+    card_set::CardSet,
+    hand_rank::{HandClass, HandRank},
+    lookup::{LookupTable, LOOKUP_TABLE},
+};
 use itertools::Itertools;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvaluationError {
+    InvalidCardCount(usize),
+    InvalidCard(u32),
+    DuplicateCard(Card),
+    /// The cards do not make any hand of the lookup tables.
+    UnknownHand,
+}
+
+impl fmt::Display for EvaluationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvaluationError::InvalidCardCount(n) => {
+                write!(f, "cannot evaluate {} cards, expected 5 to 7", n)
+            }
+            EvaluationError::InvalidCard(card_int) => write!(f, "{} is not a valid card", card_int),
+            EvaluationError::DuplicateCard(card) => write!(f, "card {} is used more than once", card),
+            EvaluationError::UnknownHand => write!(f, "the cards do not make a known hand"),
+        }
+    }
+}
+
+impl std::error::Error for EvaluationError {}
+
+/// The outcome of an evaluation along with the five cards that make the hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Evaluation {
+    pub rank: HandRank,
+    /// The best five cards, grouped by rank count and then ordered by rank,
+    /// e.g. the trips before the pair of a full house.
+    pub best_five: [Card; 5],
+    pub hole_cards_used: Vec<Card>,
+    pub board_cards_used: Vec<Card>,
+}
+
+impl Evaluation {
+    /// Cards of the best five that are not part of a pair, trips or quads.
+    pub fn kickers(&self) -> Vec<Card> {
+        self.best_five
+            .iter()
+            .filter(|c| self.best_five.iter().filter(|o| o.rank() == c.rank()).count() == 1)
+            .copied()
+            .collect()
+    }
+
+    /// A detailed name for the hand, e.g. "Full House, Aces full of Threes" or
+    /// "Two Pair, Kings and Sevens with a Queen kicker".
+    pub fn description(&self) -> String {
+        let ranks = self.best_five.map(|c| c.rank());
+        let with_kicker = |kicker: Rank| {
+            let article = match kicker {
+                Rank::Ace | Rank::Eight => "an",
+                _ => "a",
+            };
+            format!("with {} {} kicker", article, kicker.name())
+        };
+        let is_wheel = ranks[0] == Rank::Ace && ranks[1] == Rank::Five;
+        let class = self.rank.class();
+        match class {
+            HandClass::StraightFlush if ranks[0] == Rank::Ace && !is_wheel => "Royal Flush".to_string(),
+            HandClass::StraightFlush | HandClass::Straight if is_wheel => {
+                format!("{}, Five high (wheel)", class)
+            }
+            HandClass::StraightFlush | HandClass::Straight | HandClass::Flush => {
+                format!("{}, {} high", class, ranks[0].name())
+            }
+            HandClass::FourOfAKind => {
+                format!("{}, {} {}", class, ranks[0].plural_name(), with_kicker(ranks[4]))
+            }
+            HandClass::FullHouse => {
+                format!("{}, {} full of {}", class, ranks[0].plural_name(), ranks[3].plural_name())
+            }
+            HandClass::ThreeOfAKind => {
+                format!("{}, {} {}", class, ranks[0].plural_name(), with_kicker(ranks[3]))
+            }
+            HandClass::TwoPair => format!(
+                "{}, {} and {} {}",
+                class,
+                ranks[0].plural_name(),
+                ranks[2].plural_name(),
+                with_kicker(ranks[4])
+            ),
+            HandClass::Pair => {
+                format!("{}, {} {}", class, ranks[0].plural_name(), with_kicker(ranks[2]))
+            }
+            HandClass::HighCard => format!("{}, {}", class, ranks[0].name()),
+        }
+    }
+}
+
+/// Scores poker hands with deuces' variant of Cactus Kev's algorithm. Hand
+/// ranks run from 1 (royal flush) to 7462 (7-5-4-3-2 unsuited), lower is better.
+///
+/// ```
+/// use deuces_rs::{Card, Evaluator};
+///
+/// let evaluator = Evaluator::new();
+/// let hand = Card::hand_to_binary(&["Ah", "Kh"]).unwrap();
+/// let board = Card::hand_to_binary(&["Qh", "Jh", "Th", "2c", "3d"]).unwrap();
+/// let rank = evaluator.evaluate(&hand, &board).unwrap();
+/// let class = evaluator.get_rank_class(rank);
+/// assert_eq!(rank.value(), 1);
+/// assert_eq!(evaluator.class_to_string(class), "Straight Flush");
+/// ```
+///
+/// The lookup tables are computed at compile time, so an `Evaluator` is free
+/// to construct; `EVALUATOR` is a shared instance.
+#[derive(Clone, Copy)]
 pub struct Evaluator {
-    table: LookupTable,
-    //hand_size_map: HashMap<u8, fn() -> ()>,  // Assuming these functions return nothing
+    table: &'static LookupTable,
 }
 
-impl Evaluator {
-    pub fn new() -> Self {
-        // let mut hand_size_map = HashMap::new();
-        // hand_size_map.insert(5, Self::_five);
-        // hand_size_map.insert(6, Self::_six);
-        // hand_size_map.insert(7, Self::_seven);
+pub static EVALUATOR: Evaluator = Evaluator::new();
 
+impl Evaluator {
+    pub const fn new() -> Self {
         Self {
-            table: LookupTable::new(),
-            // hand_size_map,
+            table: &LOOKUP_TABLE,
         }
     }
 
-    pub fn evaluate(&self, cards: Vec<u32>, board: Vec<u32>) -> u32 {
-        let mut all_cards = cards;
-        all_cards.extend(board);
-        self._seven(&[all_cards[0], all_cards[1], all_cards[2], all_cards[3], all_cards[4], all_cards[5], all_cards[6]])
-        //match all_cards.len() {
-        //    5 => self._five(&[all_cards[0], all_cards[1], all_cards[2], all_cards[3], all_cards[4]]),
-        //    6 => self._six(&[all_cards[0], all_cards[1], all_cards[2], all_cards[3], all_cards[4], all_cards[5]]),
-        //    7 => self._seven(&[all_cards[0], all_cards[1], all_cards[2], all_cards[3], all_cards[4], all_cards[5], all_cards[6]]),
-        //    _ => panic!("Invalid number of cards"),
-        //}
+    /// Scores the hole cards together with a board of any size, as long as the
+    /// total is between 5 and 7 distinct cards.
+    pub fn evaluate(&self, cards: &[u32], board: &[u32]) -> Result<HandRank, EvaluationError> {
+        let n = cards.len() + board.len();
+        if !(5..=7).contains(&n) {
+            return Err(EvaluationError::InvalidCardCount(n));
+        }
+        let mut all_cards = [0; 7];
+        all_cards[..cards.len()].copy_from_slice(cards);
+        all_cards[cards.len()..n].copy_from_slice(board);
+        Self::check_cards(&all_cards[..n])?;
+        let rank = match n {
+            5 => self._five(&[all_cards[0], all_cards[1], all_cards[2], all_cards[3], all_cards[4]]),
+            6 => self._six(&[all_cards[0], all_cards[1], all_cards[2], all_cards[3], all_cards[4], all_cards[5]]),
+            _ => self._seven(&all_cards),
+        };
+        rank.map(HandRank).ok_or(EvaluationError::UnknownHand)
     }
 
-    pub fn _five(&self, cards: &[u32; 5]) -> u32 {
+    /// Rejects integers that are not cards and cards given twice, which would
+    /// otherwise be scored as a wrong hand.
+    fn check_cards(cards: &[u32]) -> Result<(), EvaluationError> {
+        let mut seen = CardSet::new();
+        for &card_int in cards {
+            let card = Card::from_int(card_int).ok_or(EvaluationError::InvalidCard(card_int))?;
+            if !seen.insert(card) {
+                return Err(EvaluationError::DuplicateCard(card));
+            }
+        }
+        Ok(())
+    }
+
+    /// The raw rank of five cards, `None` if they are not five distinct cards.
+    pub(crate) fn _five(&self, cards: &[u32; 5]) -> Option<u32> {
         // if flush
+        let hand_or = (cards[0] | cards[1] | cards[2] | cards[3] | cards[4]) >> 16;
         if cards[0] & cards[1] & cards[2] & cards[3] & cards[4] & 0xF000 != 0 {
-            let hand_or = (cards[0] | cards[1] | cards[2] | cards[3] | cards[4]) >> 16;
-            let prime = Card::prime_product_from_rankbits(hand_or);
-            return *self.table.flush_lookup.get(&prime).unwrap();
+            Self::nonzero(self.table.flush_lookup[hand_or as usize])
+        }
+        // five distinct ranks: straights and high cards
+        else if hand_or.count_ones() == 5 {
+            Self::nonzero(self.table.unique_lookup[hand_or as usize])
         }
         // otherwise
         else {
             let prime = Card::prime_product_from_hand(cards);
-            return *self.table.unsuited_lookup.get(&prime).unwrap();
+            self.table.multiples_rank(prime)
+        }
+    }
+
+    pub(crate) fn _six(&self, cards: &[u32; 6]) -> Option<u32> {
+        self.direct(cards)
+    }
+
+    pub(crate) fn _seven(&self, cards: &[u32; 7]) -> Option<u32> {
+        self.direct(cards)
+    }
+
+    /// Empty table entries hold 0, which is not a rank.
+    fn nonzero(rank: u16) -> Option<u32> {
+        (rank != 0).then_some(rank as u32)
+    }
+
+    /// Scores 5 to 7 cards without enumerating 5 card combinations. The best
+    /// five ranks are picked from the rank histogram and scored with a single
+    /// table lookup.
+    fn direct(&self, cards: &[u32]) -> Option<u32> {
+        let mut suit_bits = [0u32; 4];
+        let mut counts = [0u8; 13];
+        let mut rank_bits = 0;
+        for &c in cards {
+            let bitrank = Card::get_bitrank_int(c);
+            suit_bits[Card::get_suit_int(c).trailing_zeros() as usize & 3] |= bitrank;
+            counts[Card::get_rank_int(c) as usize] += 1;
+            rank_bits |= bitrank;
+        }
+
+        // With at most seven cards a flush leaves too few cards for quads or
+        // a full house, so the flush suit alone decides the hand.
+        if let Some(&flush_bits) = suit_bits.iter().find(|b| b.count_ones() >= 5) {
+            let best = Self::straight_bits(flush_bits).unwrap_or_else(|| Self::top_bits(flush_bits, 5));
+            return Self::nonzero(self.table.flush_lookup[best as usize]);
+        }
+
+        let mut quads = 0u32;
+        let mut trips = 0u32;
+        let mut pairs = 0u32;
+        for (rank, &count) in counts.iter().enumerate() {
+            match count {
+                4 => quads |= 1 << rank,
+                3 => trips |= 1 << rank,
+                2 => pairs |= 1 << rank,
+                _ => (),
+            }
+        }
+
+        let primes = Card::PRIMES;
+        let prime = |bits: u32| primes[Self::highest_rank(bits)];
+        let product = if quads != 0 {
+            let kicker = Self::top_bits(rank_bits & !quads, 1);
+            prime(quads).pow(4) * prime(kicker)
+        } else if trips != 0 && (trips.count_ones() > 1 || pairs != 0) {
+            let three = Self::top_bits(trips, 1);
+            let two = Self::top_bits((trips & !three) | pairs, 1);
+            prime(three).pow(3) * prime(two).pow(2)
+        } else if let Some(straight) = Self::straight_bits(rank_bits) {
+            return Self::nonzero(self.table.unique_lookup[straight as usize]);
+        } else if trips != 0 {
+            let kickers = Self::top_bits(rank_bits & !trips, 2);
+            prime(trips).pow(3) * Card::prime_product_from_rankbits(kickers)
+        } else if pairs.count_ones() >= 2 {
+            let two_pair = Self::top_bits(pairs, 2);
+            let kicker = Self::top_bits(rank_bits & !two_pair, 1);
+            Card::prime_product_from_rankbits(two_pair).pow(2) * prime(kicker)
+        } else if pairs != 0 {
+            let kickers = Self::top_bits(rank_bits & !pairs, 3);
+            prime(pairs).pow(2) * Card::prime_product_from_rankbits(kickers)
+        } else {
+            return Self::nonzero(self.table.unique_lookup[Self::top_bits(rank_bits, 5) as usize]);
+        };
+        self.table.multiples_rank(product)
+    }
+
+    fn highest_rank(bits: u32) -> usize {
+        31 - bits.leading_zeros() as usize
+    }
+
+    /// Keeps the `n` highest set bits.
+    fn top_bits(mut bits: u32, n: u32) -> u32 {
+        while bits.count_ones() > n {
+            bits &= bits - 1;
         }
+        bits
+    }
+
+    /// The highest five card straight contained in the rank bits, if any.
+    fn straight_bits(bits: u32) -> Option<u32> {
+        const WHEEL: u32 = 0b1000000001111;
+        (0..9)
+            .rev()
+            .map(|low| 0b11111 << low)
+            .find(|&straight| bits & straight == straight)
+            .or(if bits & WHEEL == WHEEL { Some(WHEEL) } else { None })
     }
 
-    pub fn _seven(&self, cards: &[u32; 7]) -> u32 {
+    /// Returns the best score and the indices of the five cards that make it.
+    fn best_combination(&self, cards: &[u32]) -> Option<(u32, [usize; 5])> {
         let mut minimum = LookupTable::MAX_HIGH_CARD;
+        let mut best = [0, 1, 2, 3, 4];
 
-        let all5cardcombos: Vec<_> = cards.iter().combinations(5).collect();
+        let all5cardcombos: Vec<_> = (0..cards.len()).combinations(5).collect();
         for combo in all5cardcombos {
-            let score = self._five(&[*combo[0], *combo[1], *combo[2], *combo[3], *combo[4]]);
+            let combo = [combo[0], combo[1], combo[2], combo[3], combo[4]];
+            let score = self._five(&combo.map(|i| cards[i]))?;
             if score < minimum {
                 minimum = score;
+                best = combo;
             }
         }
 
-        minimum
-    }
-
-    pub fn get_rank_class(&self, hr: u32) -> Result<u32, &'static str> {
-        if hr <= LookupTable::MAX_STRAIGHT_FLUSH {
-            Ok(self.table.max_to_rank_class_lookup[&LookupTable::MAX_STRAIGHT_FLUSH])
-        } else if hr <= LookupTable::MAX_FOUR_OF_A_KIND {
-            Ok(self.table.max_to_rank_class_lookup[&LookupTable::MAX_FOUR_OF_A_KIND])
-        } else if hr <= LookupTable::MAX_FULL_HOUSE {
-            Ok(self.table.max_to_rank_class_lookup[&LookupTable::MAX_FULL_HOUSE])
-        } else if hr <= LookupTable::MAX_FLUSH {
-            Ok(self.table.max_to_rank_class_lookup[&LookupTable::MAX_FLUSH])
-        } else if hr <= LookupTable::MAX_STRAIGHT {
-            Ok(self.table.max_to_rank_class_lookup[&LookupTable::MAX_STRAIGHT])
-        } else if hr <= LookupTable::MAX_THREE_OF_A_KIND {
-            Ok(self.table.max_to_rank_class_lookup[&LookupTable::MAX_THREE_OF_A_KIND])
-        } else if hr <= LookupTable::MAX_TWO_PAIR {
-            Ok(self.table.max_to_rank_class_lookup[&LookupTable::MAX_TWO_PAIR])
-        } else if hr <= LookupTable::MAX_PAIR {
-            Ok(self.table.max_to_rank_class_lookup[&LookupTable::MAX_PAIR])
-        } else if hr <= LookupTable::MAX_HIGH_CARD {
-            Ok(self.table.max_to_rank_class_lookup[&LookupTable::MAX_HIGH_CARD])
-        } else {
-            Err("Invalid hand rank, cannot return rank class")
+        Some((minimum, best))
+    }
+
+    /// Like `evaluate`, but also reports which five cards make the hand and
+    /// where they came from.
+    pub fn evaluate_best_five(&self, cards: &[Card], board: &[Card]) -> Result<Evaluation, EvaluationError> {
+        let all_cards: Vec<u32> = cards.iter().chain(board).map(|c| c.0).collect();
+        if !(5..=7).contains(&all_cards.len()) {
+            return Err(EvaluationError::InvalidCardCount(all_cards.len()));
         }
+        Self::check_cards(&all_cards)?;
+        let (rank, indices) = self.best_combination(&all_cards).ok_or(EvaluationError::UnknownHand)?;
+        let unsorted = indices.map(|i| Card(all_cards[i]));
+        let mut best_five = unsorted;
+        best_five.sort_by_key(|c| {
+            let count = unsorted.iter().filter(|o| o.rank() == c.rank()).count();
+            std::cmp::Reverse((count, c.rank(), c.suit()))
+        });
+        Ok(Evaluation {
+            rank: HandRank(rank),
+            best_five,
+            hole_cards_used: indices.iter().filter(|&&i| i < cards.len()).map(|&i| Card(all_cards[i])).collect(),
+            board_cards_used: indices.iter().filter(|&&i| i >= cards.len()).map(|&i| Card(all_cards[i])).collect(),
+        })
     }
 
-    pub fn get_five_card_rank_percentage(&self, hand_rank: u32) -> f64 {
-        hand_rank as f64 / LookupTable::MAX_HIGH_CARD as f64
+    /// Returns the class of a hand rank, from straight flush to high card.
+    pub fn get_rank_class(&self, hr: HandRank) -> HandClass {
+        hr.class()
     }
 
-    pub fn class_to_string(&self, class_int: u32) -> String {
-        self.table.rank_class_to_string_lookup.get(&class_int).unwrap().to_string()
+    /// Scales a hand rank to the [0.0, 1.0] range, 0.0 being the best hand.
+    pub fn get_five_card_rank_percentage(&self, hand_rank: HandRank) -> f64 {
+        hand_rank.percentage()
     }
 
+    /// Converts a rank class to its name, e.g. "Full House".
+    pub fn class_to_string(&self, class: HandClass) -> String {
+        class.to_string()
+    }
+}
+
+impl Default for Evaluator {
+    fn default() -> Self {
+        Evaluator::new()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{card::Suit, deck::Deck};
+    use std::collections::{HashMap, HashSet};
+
+    fn cards(s: &[&str]) -> Vec<u32> {
+        Card::hand_to_binary(s).unwrap()
+    }
+
+    #[test]
+    fn test_evaluate_streets() {
+        let evaluator = Evaluator::new();
+        let hand = cards(&["Ah", "Kh"]);
+        let flop = evaluator.evaluate(&hand, &cards(&["Qh", "Jh", "2c"])).unwrap();
+        let turn = evaluator.evaluate(&hand, &cards(&["Qh", "Jh", "2c", "Th"])).unwrap();
+        let river = evaluator.evaluate(&hand, &cards(&["Qh", "Jh", "2c", "Th", "3d"])).unwrap();
+        assert_eq!(evaluator.get_rank_class(flop), HandClass::HighCard);
+        assert_eq!(turn, HandRank::BEST);
+        assert_eq!(river, HandRank::BEST);
+        assert_eq!(
+            evaluator.evaluate(&hand, &cards(&["Qh", "Jh"])),
+            Err(EvaluationError::InvalidCardCount(4))
+        );
+        assert_eq!(
+            evaluator.evaluate(&hand, &cards(&["Qh", "Jh", "2c", "Th", "3d", "4d"])),
+            Err(EvaluationError::InvalidCardCount(8))
+        );
+    }
+
+    #[test]
+    fn test_evaluate_invalid_cards() {
+        let evaluator = Evaluator::new();
+        let aces = cards(&["As", "As"]);
+        let spade = Card::new("As").unwrap();
+        assert_eq!(
+            evaluator.evaluate(&aces, &cards(&["As", "As", "As"])),
+            Err(EvaluationError::DuplicateCard(spade))
+        );
+        assert_eq!(
+            evaluator.evaluate(&cards(&["As", "Kd"]), &cards(&["Qh", "Jh", "As", "2c"])),
+            Err(EvaluationError::DuplicateCard(spade))
+        );
+        assert_eq!(evaluator.evaluate(&[0; 2], &[0; 3]), Err(EvaluationError::InvalidCard(0)));
+        assert_eq!(evaluator._five(&[0; 5]), None);
+        assert_eq!(evaluator._five(&[spade.0; 5]), None);
+        assert_eq!(Card::from_int(spade.0), Some(spade));
+        assert_eq!(Card::from_int(spade.0 ^ 1), None);
+    }
+
+    #[test]
+    fn test_evaluate_best_five() {
+        let evaluator = Evaluator::new();
+        let parse = |s: &[&str]| -> Vec<Card> { s.iter().map(|c| Card::new(c).unwrap()).collect() };
+        let hand = parse(&["Kd", "7s"]);
+        let board = parse(&["Kh", "7c", "Qs", "2d", "Kc"]);
+        let evaluation = evaluator.evaluate_best_five(&hand, &board).unwrap();
+        let board_ints: Vec<u32> = board.iter().map(|c| c.0).collect();
+        assert_eq!(evaluation.rank, evaluator.evaluate(&cards(&["Kd", "7s"]), &board_ints).unwrap());
+        assert_eq!(evaluation.best_five.to_vec(), parse(&["Kc", "Kd", "Kh", "7c", "7s"]));
+        assert_eq!(evaluation.hole_cards_used, hand);
+        assert_eq!(evaluation.board_cards_used, parse(&["Kh", "7c", "Kc"]));
+        assert!(evaluation.kickers().is_empty());
+
+        let board = parse(&["Ah", "Jc", "9s", "4d", "3c"]);
+        let evaluation = evaluator.evaluate_best_five(&hand, &board).unwrap();
+        assert_eq!(evaluation.best_five.to_vec(), parse(&["Ah", "Kd", "Jc", "9s", "7s"]));
+        assert_eq!(evaluation.kickers().len(), 5);
+        assert_eq!(
+            evaluator.evaluate_best_five(&hand, &board[..2]),
+            Err(EvaluationError::InvalidCardCount(4))
+        );
+        assert_eq!(
+            evaluator.evaluate_best_five(&parse(&["Ah", "Ah"]), &parse(&["Ah", "Kd", "Qd"])),
+            Err(EvaluationError::DuplicateCard(parse(&["Ah"])[0]))
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let evaluator = Evaluator::new();
+        let describe = |hand: &str, board: &str| {
+            let hand: Vec<Card> = hand.split(' ').map(|c| Card::new(c).unwrap()).collect();
+            let board: Vec<Card> = board.split(' ').map(|c| Card::new(c).unwrap()).collect();
+            evaluator.evaluate_best_five(&hand, &board).unwrap().description()
+        };
+        assert_eq!(describe("Ah Kh", "Qh Jh Th 2c 3d"), "Royal Flush");
+        assert_eq!(describe("9h Kh", "Qh Jh Th 2c 3d"), "Straight Flush, King high");
+        assert_eq!(describe("Ah 2h", "3h 4h 5h"), "Straight Flush, Five high (wheel)");
+        assert_eq!(describe("7c 7d", "7h 7s Ad 2c"), "Four of a Kind, Sevens with an Ace kicker");
+        assert_eq!(describe("Ac As", "2h 2s 3c 3d 3h"), "Full House, Threes full of Aces");
+        assert_eq!(describe("Ad 2c", "2h 2s 3c 3d 3h"), "Full House, Threes full of Twos");
+        assert_eq!(describe("Ah 9h", "6h 4h 2h Kd Kc"), "Flush, Ace high");
+        assert_eq!(describe("Ac 2d", "3h 4s 5c Kd"), "Straight, Five high (wheel)");
+        assert_eq!(describe("Tc 9d", "8h 7s 6c 5d"), "Straight, Ten high");
+        assert_eq!(describe("Qc Qd", "Qh 7s 2c"), "Three of a Kind, Queens with a Seven kicker");
+        assert_eq!(describe("Kc 7d", "Kh 7s Qc 2d 3h"), "Two Pair, Kings and Sevens with a Queen kicker");
+        assert_eq!(describe("Jc Jd", "8h 7s 2c"), "Pair, Jacks with an Eight kicker");
+        assert_eq!(describe("Ac 9d", "8h 7s 2c"), "High Card, Ace");
+    }
+
+    /// Checks the direct evaluation of 6 and 7 cards against the best of the
+    /// 5 card combinations. The direct result only depends on the rank
+    /// histogram and, for flushes, on the ranks of the flush suit, so every
+    /// such case is enumerated once.
+    #[test]
+    fn test_direct_matches_combinations() {
+        let evaluator = Evaluator::new();
+        let check = |hand: &[u32]| {
+            assert_eq!(
+                evaluator.direct(hand).unwrap(),
+                evaluator.best_combination(hand).unwrap().0,
+                "{:?}",
+                hand.iter().map(|&c| Card::int_to_str(c)).collect::<Vec<_>>()
+            );
+        };
+        let card = |rank: usize, suit: usize| Card::from_parts(Rank::ALL[rank], Suit::ALL[suit]).0;
+
+        for n in [6, 7] {
+            // every rank histogram, dealing suits round-robin so that no suit
+            // has more than two cards
+            for ranks in (0..13).combinations_with_replacement(n) {
+                if ranks.windows(5).any(|w| w[0] == w[4]) {
+                    continue;
+                }
+                let hand: Vec<u32> = ranks.iter().enumerate().map(|(i, &r)| card(r, i % 4)).collect();
+                check(&hand);
+            }
+
+            // every set of flush ranks, with every choice of off-suit ranks
+            for flush_len in 5..=n {
+                for flush_ranks in (0..13).combinations(flush_len) {
+                    for others in (0..13).combinations_with_replacement(n - flush_len) {
+                        let mut hand: Vec<u32> = flush_ranks.iter().map(|&r| card(r, 0)).collect();
+                        hand.extend(others.iter().enumerate().map(|(i, &r)| card(r, 1 + i)));
+                        check(&hand);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Calls `f` with every `k` card combination of the 52 card deck.
+    fn for_each_hand<const K: usize>(mut f: impl FnMut(&[u32; K])) {
+        let deck: Vec<u32> = Deck::full_deck().iter().map(|c| c.0).collect();
+        let mut indices: [usize; K] = std::array::from_fn(|i| i);
+        loop {
+            f(&indices.map(|i| deck[i]));
+            let Some(i) = (0..K).rev().find(|&i| indices[i] < deck.len() - K + i) else {
+                return;
+            };
+            indices[i] += 1;
+            for j in i + 1..K {
+                indices[j] = indices[j - 1] + 1;
+            }
+        }
+    }
+
+    fn class_counts<const K: usize>(score: impl Fn(&[u32; K]) -> Option<u32>) -> (HashMap<HandClass, u64>, HashSet<u32>) {
+        let mut counts = HashMap::new();
+        let mut ranks = HashSet::new();
+        for_each_hand(|hand: &[u32; K]| {
+            let rank = score(hand).unwrap();
+            *counts.entry(HandRank(rank).class()).or_insert(0) += 1;
+            ranks.insert(rank);
+        });
+        (counts, ranks)
+    }
+
+    #[test]
+    fn test_all_five_card_hands() {
+        let evaluator = Evaluator::new();
+        let (counts, ranks) = class_counts(|hand: &[u32; 5]| evaluator._five(hand));
+
+        assert_eq!(counts[&HandClass::StraightFlush], 40);
+        assert_eq!(counts[&HandClass::FourOfAKind], 624);
+        assert_eq!(counts[&HandClass::FullHouse], 3744);
+        assert_eq!(counts[&HandClass::Flush], 5108);
+        assert_eq!(counts[&HandClass::Straight], 10200);
+        assert_eq!(counts[&HandClass::ThreeOfAKind], 54912);
+        assert_eq!(counts[&HandClass::TwoPair], 123552);
+        assert_eq!(counts[&HandClass::Pair], 1098240);
+        assert_eq!(counts[&HandClass::HighCard], 1302540);
+        assert_eq!(counts.values().sum::<u64>(), 2598960);
+        assert_eq!(ranks.len(), 7462);
+    }
+
+    /// Takes a while, run with `cargo test --release --features exhaustive`.
+    #[cfg(feature = "exhaustive")]
+    #[test]
+    fn test_all_seven_card_hands() {
+        let evaluator = Evaluator::new();
+        let (counts, ranks) = class_counts(|hand: &[u32; 7]| evaluator._seven(hand));
+
+        assert_eq!(counts[&HandClass::StraightFlush], 41584);
+        assert_eq!(counts[&HandClass::FourOfAKind], 224848);
+        assert_eq!(counts[&HandClass::FullHouse], 3473184);
+        assert_eq!(counts[&HandClass::Flush], 4047644);
+        assert_eq!(counts[&HandClass::Straight], 6180020);
+        assert_eq!(counts[&HandClass::ThreeOfAKind], 6461620);
+        assert_eq!(counts[&HandClass::TwoPair], 31433400);
+        assert_eq!(counts[&HandClass::Pair], 58627800);
+        assert_eq!(counts[&HandClass::HighCard], 23294460);
+        assert_eq!(counts.values().sum::<u64>(), 133784560);
+        assert_eq!(ranks.len(), 4824);
+    }
+
+    #[test]
+    fn test_six_matches_seven() {
+        let evaluator = Evaluator::new();
+        let six = cards(&["As", "Ad", "Kc", "Kd", "2h", "7s"]);
+        let seven = cards(&["As", "Ad", "Kc", "Kd", "2h", "7s", "3c"]);
+        assert_eq!(
+            evaluator._six(&[six[0], six[1], six[2], six[3], six[4], six[5]]),
+            evaluator._seven(&[seven[0], seven[1], seven[2], seven[3], seven[4], seven[5], seven[6]])
+        );
+    }
 
     #[test]
     fn test_get_rank_class() {
         let evaluator = Evaluator::new();
+        let rank_class = |hr: u32| evaluator.get_rank_class(HandRank::new(hr).unwrap()).to_int();
+        let max_to_rank_class = |max: u32| {
+            LookupTable::MAX_TO_RANK_CLASS.iter().find(|&&(m, _)| m == max).unwrap().1
+        };
 
-        assert_eq!(evaluator.get_rank_class(0).unwrap(), evaluator.table.max_to_rank_class_lookup[&LookupTable::MAX_STRAIGHT_FLUSH]);
-        assert_eq!(evaluator.get_rank_class(LookupTable::MAX_STRAIGHT_FLUSH).unwrap(), evaluator.table.max_to_rank_class_lookup[&LookupTable::MAX_STRAIGHT_FLUSH]);
-        assert_eq!(evaluator.get_rank_class(LookupTable::MAX_FOUR_OF_A_KIND).unwrap(), evaluator.table.max_to_rank_class_lookup[&LookupTable::MAX_FOUR_OF_A_KIND]);
-        assert_eq!(evaluator.get_rank_class(LookupTable::MAX_FULL_HOUSE).unwrap(), evaluator.table.max_to_rank_class_lookup[&LookupTable::MAX_FULL_HOUSE]);
+        assert_eq!(rank_class(1), max_to_rank_class(LookupTable::MAX_STRAIGHT_FLUSH));
+        assert_eq!(rank_class(LookupTable::MAX_STRAIGHT_FLUSH), max_to_rank_class(LookupTable::MAX_STRAIGHT_FLUSH));
+        assert_eq!(rank_class(LookupTable::MAX_FOUR_OF_A_KIND), max_to_rank_class(LookupTable::MAX_FOUR_OF_A_KIND));
+        assert_eq!(rank_class(LookupTable::MAX_FULL_HOUSE), max_to_rank_class(LookupTable::MAX_FULL_HOUSE));
         // Continue for other hand ranks...
 
-        match evaluator.get_rank_class(LookupTable::MAX_HIGH_CARD + 1) {
-            Ok(_) => panic!("Should have returned an error for invalid hand rank"),
-            Err(e) => assert_eq!(e, "Invalid hand rank, cannot return rank class"),
+        assert_eq!(HandRank::new(LookupTable::MAX_HIGH_CARD + 1), None);
+    }
+
+    #[test]
+    fn test_class_to_string() {
+        let evaluator = Evaluator::new();
+        for (class_int, name) in LookupTable::RANK_CLASS_TO_STRING {
+            let class = HandClass::from_int(class_int).unwrap();
+            assert_eq!(evaluator.class_to_string(class), name);
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,297 @@
+//! Provably fair shuffling with a commit-reveal protocol.
+//!
+//! 1. The server draws a [`ServerSeed`] from the operating system CSPRNG and
+//!    publishes its [`Commitment`], the SHA-256 of the seed, before the hand.
+//! 2. Players contribute client seeds, so that the server cannot pick an
+//!    order of cards on its own.
+//! 3. The deck is shuffled with ChaCha20 keyed by
+//!    `SHA-256(server_seed || len(client_seed) || client_seed || ...)`, each
+//!    length being a big endian `u64`.
+//! 4. After the hand the server reveals its seed and anyone can [`verify`]
+//!    the commitment and recompute the order of the cards.
+//!
+//! The shuffle is a Fisher-Yates pass over the `CARDS` order used by
+//! [`GameDealer`](crate::builder::GameDealer): for each `i` from 1 to 51 a
+//! uniform `j` in `0..=i` is drawn by rejection sampling of `next_u32` and
+//! cards `i` and `j` are swapped.
+
+use std::fmt;
+use std::str::FromStr;
+
+use rand::rngs::OsRng;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use sha2::{Digest, Sha256};
+
+use crate::builder::{CardShuffler, CARDS};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FairnessError {
+    InvalidHex(String),
+    CommitmentMismatch,
+    ShuffleMismatch,
+}
+
+impl fmt::Display for FairnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FairnessError::InvalidHex(s) => write!(f, "invalid 32 byte hex string: {:?}", s),
+            FairnessError::CommitmentMismatch => write!(f, "server seed does not match the commitment"),
+            FairnessError::ShuffleMismatch => write!(f, "cards do not match the seeded shuffle"),
+        }
+    }
+}
+
+impl std::error::Error for FairnessError {}
+
+fn to_hex(bytes: &[u8; 32]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(s: &str) -> Result<[u8; 32], FairnessError> {
+    let invalid = || FairnessError::InvalidHex(s.to_string());
+    if s.len() != 64 || !s.is_ascii() {
+        return Err(invalid());
+    }
+    let nibble = |c: u8| (c as char).to_digit(16).map(|d| d as u8).ok_or_else(invalid);
+    let mut bytes = [0; 32];
+    for (byte, pair) in bytes.iter_mut().zip(s.as_bytes().chunks(2)) {
+        *byte = nibble(pair[0])? << 4 | nibble(pair[1])?;
+    }
+    Ok(bytes)
+}
+
+/// The secret half of the shuffle seed, kept by the server until the hand
+/// is over. Its `Debug` output is redacted so that it cannot leak in logs.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ServerSeed([u8; 32]);
+
+impl fmt::Debug for ServerSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ServerSeed(<redacted>)")
+    }
+}
+
+impl ServerSeed {
+    /// Draws a new seed from the operating system CSPRNG.
+    pub fn generate() -> Self {
+        let mut bytes = [0; 32];
+        OsRng.fill_bytes(&mut bytes);
+        ServerSeed(bytes)
+    }
+
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        ServerSeed(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    pub fn commitment(&self) -> Commitment {
+        Commitment(Sha256::digest(self.0).into())
+    }
+}
+
+impl fmt::Display for ServerSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&to_hex(&self.0))
+    }
+}
+
+impl FromStr for ServerSeed {
+    type Err = FairnessError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_hex(s).map(ServerSeed)
+    }
+}
+
+/// SHA-256 of a server seed, published before the hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Commitment([u8; 32]);
+
+impl Commitment {
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Commitment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&to_hex(&self.0))
+    }
+}
+
+impl FromStr for Commitment {
+    type Err = FairnessError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_hex(s).map(Commitment)
+    }
+}
+
+/// Shuffler keyed by a committed server seed and the client seeds of the hand.
+#[derive(Debug, Clone)]
+pub struct ProvablyFairShuffler {
+    server_seed: ServerSeed,
+    client_seeds: Vec<String>,
+}
+
+impl ProvablyFairShuffler {
+    pub fn new() -> Self {
+        ProvablyFairShuffler::with_server_seed(ServerSeed::generate())
+    }
+
+    pub fn with_server_seed(server_seed: ServerSeed) -> Self {
+        ProvablyFairShuffler {
+            server_seed,
+            client_seeds: Vec::new(),
+        }
+    }
+
+    /// The commitment to publish before any client seed is accepted.
+    pub fn commitment(&self) -> Commitment {
+        self.server_seed.commitment()
+    }
+
+    pub fn add_client_seed(&mut self, client_seed: impl Into<String>) {
+        self.client_seeds.push(client_seed.into());
+    }
+
+    pub fn client_seeds(&self) -> &[String] {
+        &self.client_seeds
+    }
+
+    /// The server seed, to be revealed once the hand is over.
+    pub fn reveal(&self) -> &ServerSeed {
+        &self.server_seed
+    }
+}
+
+impl Default for ProvablyFairShuffler {
+    fn default() -> Self {
+        ProvablyFairShuffler::new()
+    }
+}
+
+impl CardShuffler for ProvablyFairShuffler {
+    fn shuffle(&self) -> Vec<&'static str> {
+        shuffled_cards(&self.server_seed, &self.client_seeds)
+    }
+}
+
+fn uniform_below<R: RngCore>(rng: &mut R, bound: u32) -> u32 {
+    let zone = u32::MAX - u32::MAX % bound;
+    loop {
+        let value = rng.next_u32();
+        if value < zone {
+            return value % bound;
+        }
+    }
+}
+
+/// Recomputes the order of the cards from revealed seeds.
+pub fn shuffled_cards<S: AsRef<str>>(server_seed: &ServerSeed, client_seeds: &[S]) -> Vec<&'static str> {
+    let mut hasher = Sha256::new();
+    hasher.update(server_seed.as_bytes());
+    for client_seed in client_seeds {
+        let client_seed = client_seed.as_ref().as_bytes();
+        hasher.update((client_seed.len() as u64).to_be_bytes());
+        hasher.update(client_seed);
+    }
+    let mut rng = ChaCha20Rng::from_seed(hasher.finalize().into());
+
+    let mut cards = CARDS;
+    for i in 1..cards.len() {
+        let j = uniform_below(&mut rng, i as u32 + 1) as usize;
+        cards.swap(i, j);
+    }
+    cards.to_vec()
+}
+
+/// Checks that the revealed server seed matches the commitment published
+/// before the hand and that `cards` is the shuffle of the revealed seeds.
+pub fn verify<S: AsRef<str>>(
+    commitment: &Commitment,
+    server_seed: &ServerSeed,
+    client_seeds: &[S],
+    cards: &[&str],
+) -> Result<(), FairnessError> {
+    if server_seed.commitment() != *commitment {
+        return Err(FairnessError::CommitmentMismatch);
+    }
+    if shuffled_cards(server_seed, client_seeds) != cards {
+        return Err(FairnessError::ShuffleMismatch);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{Dealer, GameDealer};
+
+    #[test]
+    fn test_commit_reveal() {
+        let mut shuffler = ProvablyFairShuffler::new();
+        let commitment = shuffler.commitment();
+        shuffler.add_client_seed("alice");
+        shuffler.add_client_seed("bob");
+        let cards = shuffler.shuffle();
+
+        let published: Commitment = commitment.to_string().parse().unwrap();
+        let revealed: ServerSeed = shuffler.reveal().to_string().parse().unwrap();
+        assert_eq!(verify(&published, &revealed, &["alice", "bob"], &cards), Ok(()));
+
+        assert_eq!(
+            verify(&published, &ServerSeed::generate(), &["alice", "bob"], &cards),
+            Err(FairnessError::CommitmentMismatch)
+        );
+        assert_eq!(
+            verify(&published, &revealed, &["alice", "eve"], &cards),
+            Err(FairnessError::ShuffleMismatch)
+        );
+        // client seeds are length prefixed, so they cannot be re-split
+        assert_ne!(shuffled_cards(&revealed, &["alice", "bob"]), shuffled_cards(&revealed, &["aliceb", "ob"]));
+    }
+
+    #[test]
+    fn test_known_seed() {
+        let server_seed = ServerSeed::from_bytes([0; 32]);
+        assert_eq!(
+            server_seed.commitment().to_string(),
+            "66687aadf862bd776c8fc18b8e9f8e20089714856ee233b3902a591d0d5f2925"
+        );
+        // the published algorithm, pinned so that past verifications stay valid
+        // and checked against an independent ChaCha20 implementation
+        let cards = shuffled_cards(&server_seed, &["client"]);
+        assert_eq!(
+            cards.join(" "),
+            "Kd 4h Qs 4s 7s Js Th 2s 9h 7d 8s 3h Jc 2h 7c 6h 9d Ad 4c 3s 5c Kc 6c Jd 2d Tc \
+             Jh Td 5s As Kh Qc 9s 9c 6d Qh 2c 7h 6s 5h 3d Ah 8c 8d 5d Ks Ac 3c Ts 8h Qd 4d"
+        );
+
+        let mut shuffler = ProvablyFairShuffler::with_server_seed(server_seed);
+        shuffler.add_client_seed("client");
+        let deal = GameDealer::new(shuffler).deal(2).unwrap();
+        assert_eq!(deal.hands[0].cards, vec![cards[1], cards[3]]);
+    }
+
+    #[test]
+    fn test_invalid_hex() {
+        assert!("00".parse::<Commitment>().is_err());
+        assert!("zz".repeat(32).parse::<ServerSeed>().is_err());
+        assert!("é".repeat(32).parse::<ServerSeed>().is_err());
+        assert!("+f".repeat(32).parse::<ServerSeed>().is_err());
+        assert_eq!("Af".repeat(32).parse::<ServerSeed>(), Ok(ServerSeed::from_bytes([0xaf; 32])));
+    }
+
+    #[test]
+    fn test_debug_redacts_server_seed() {
+        let shuffler = ProvablyFairShuffler::with_server_seed(ServerSeed::from_bytes([0xab; 32]));
+        let debug = format!("{:?}", shuffler);
+        assert!(debug.contains("ServerSeed(<redacted>)"));
+        assert!(!debug.contains("171"));
+    }
+}
@@ -0,0 +1,194 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::lookup::LookupTable;
+
+/// A deuces hand rank from 1 (royal flush) to 7462 (7-5-4-3-2 unsuited).
+/// Unlike the raw value, a better hand compares greater.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HandRank(pub(crate) u32);
+
+impl HandRank {
+    pub const BEST: HandRank = HandRank(1);
+    pub const WORST: HandRank = HandRank(LookupTable::MAX_HIGH_CARD);
+
+    /// Wraps a raw deuces rank, returning `None` outside of 1..=7462.
+    pub fn new(value: u32) -> Option<HandRank> {
+        if (1..=LookupTable::MAX_HIGH_CARD).contains(&value) {
+            Some(HandRank(value))
+        } else {
+            None
+        }
+    }
+
+    /// The raw deuces rank, lower is better.
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+
+    pub fn class(&self) -> HandClass {
+        HandClass::ALL
+            .into_iter()
+            .find(|class| self.0 <= class.max_rank())
+            .unwrap()
+    }
+
+    /// Scales the rank to the [0.0, 1.0] range, 0.0 being the best hand.
+    pub fn percentage(&self) -> f64 {
+        self.0 as f64 / LookupTable::MAX_HIGH_CARD as f64
+    }
+
+    /// Share of distinct hand ranks this hand beats, 1.0 being the best hand.
+    pub fn percentile(&self) -> f64 {
+        1.0 - self.percentage()
+    }
+}
+
+impl Ord for HandRank {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl PartialOrd for HandRank {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for HandRank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The nine hand classes, carrying the deuces class codes 1 to 9. A better
+/// class compares greater.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HandClass {
+    StraightFlush = 1,
+    FourOfAKind = 2,
+    FullHouse = 3,
+    Flush = 4,
+    Straight = 5,
+    ThreeOfAKind = 6,
+    TwoPair = 7,
+    Pair = 8,
+    HighCard = 9,
+}
+
+impl HandClass {
+    pub const ALL: [HandClass; 9] = [
+        HandClass::StraightFlush,
+        HandClass::FourOfAKind,
+        HandClass::FullHouse,
+        HandClass::Flush,
+        HandClass::Straight,
+        HandClass::ThreeOfAKind,
+        HandClass::TwoPair,
+        HandClass::Pair,
+        HandClass::HighCard,
+    ];
+
+    pub fn from_int(class_int: u32) -> Option<HandClass> {
+        HandClass::ALL.into_iter().find(|c| c.to_int() == class_int)
+    }
+
+    pub fn to_int(self) -> u32 {
+        self as u32
+    }
+
+    /// The worst hand rank belonging to this class.
+    pub fn max_rank(self) -> u32 {
+        match self {
+            HandClass::StraightFlush => LookupTable::MAX_STRAIGHT_FLUSH,
+            HandClass::FourOfAKind => LookupTable::MAX_FOUR_OF_A_KIND,
+            HandClass::FullHouse => LookupTable::MAX_FULL_HOUSE,
+            HandClass::Flush => LookupTable::MAX_FLUSH,
+            HandClass::Straight => LookupTable::MAX_STRAIGHT,
+            HandClass::ThreeOfAKind => LookupTable::MAX_THREE_OF_A_KIND,
+            HandClass::TwoPair => LookupTable::MAX_TWO_PAIR,
+            HandClass::Pair => LookupTable::MAX_PAIR,
+            HandClass::HighCard => LookupTable::MAX_HIGH_CARD,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HandClass::StraightFlush => "Straight Flush",
+            HandClass::FourOfAKind => "Four of a Kind",
+            HandClass::FullHouse => "Full House",
+            HandClass::Flush => "Flush",
+            HandClass::Straight => "Straight",
+            HandClass::ThreeOfAKind => "Three of a Kind",
+            HandClass::TwoPair => "Two Pair",
+            HandClass::Pair => "Pair",
+            HandClass::HighCard => "High Card",
+        }
+    }
+}
+
+impl Ord for HandClass {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.to_int().cmp(&self.to_int())
+    }
+}
+
+impl PartialOrd for HandClass {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl TryFrom<u32> for HandClass {
+    type Error = u32;
+
+    fn try_from(class_int: u32) -> Result<Self, Self::Error> {
+        HandClass::from_int(class_int).ok_or(class_int)
+    }
+}
+
+impl From<HandClass> for u32 {
+    fn from(class: HandClass) -> Self {
+        class.to_int()
+    }
+}
+
+impl fmt::Display for HandClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hand_rank_ordering() {
+        let royal = HandRank::new(1).unwrap();
+        let quads = HandRank::new(LookupTable::MAX_FOUR_OF_A_KIND).unwrap();
+        assert!(royal > quads);
+        assert_eq!(HandRank::BEST.max(HandRank::WORST), HandRank::BEST);
+        assert_eq!(HandRank::new(0), None);
+        assert_eq!(HandRank::new(LookupTable::MAX_HIGH_CARD + 1), None);
+        assert_eq!(HandRank::WORST.percentage(), 1.0);
+        assert_eq!(HandRank::WORST.percentile(), 0.0);
+    }
+
+    #[test]
+    fn test_hand_class() {
+        assert_eq!(HandRank::BEST.class(), HandClass::StraightFlush);
+        for class in HandClass::ALL {
+            assert_eq!(HandRank::new(class.max_rank()).unwrap().class(), class);
+            assert_eq!(HandClass::try_from(class.to_int()), Ok(class));
+        }
+        assert_eq!(
+            HandRank::new(LookupTable::MAX_FULL_HOUSE + 1).unwrap().class(),
+            HandClass::Flush
+        );
+        assert!(HandClass::FullHouse > HandClass::Flush);
+        assert_eq!(HandClass::try_from(10), Err(10));
+        assert_eq!(HandClass::TwoPair.to_string(), "Two Pair");
+    }
+}
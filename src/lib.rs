@@ -1,11 +1,56 @@
-mod lookup;
-mod evaluator;
 pub mod card;
+pub mod card_set;
 pub mod model;
 pub mod builder;
+pub mod deck;
+pub mod equity;
+pub mod fair;
+pub mod evaluator;
+pub mod hand_rank;
+pub mod live_hand;
+pub mod lookup;
+pub mod pretty;
+pub mod range;
+#[cfg(feature = "python")]
+mod python;
 
+pub use builder::DealError;
 pub use builder::GameDealer;
 pub use builder::RandomCardShuffler;
+pub use builder::RngCardShuffler;
+pub use builder::SeededCardShuffler;
+pub use card::Card;
+pub use card::Rank;
+pub use card::Suit;
+pub use card::CardParseError;
+pub use card_set::CardSet;
+pub use deck::Deck;
+pub use deck::DeckError;
+pub use equity::Budget;
+pub use equity::Equity;
+pub use equity::EquityError;
+pub use equity::Estimate;
+pub use equity::MonteCarlo;
+pub use equity::PlayerEquity;
+pub use evaluator::Evaluation;
+pub use evaluator::EvaluationError;
+pub use evaluator::Evaluator;
+pub use evaluator::EVALUATOR;
+pub use fair::Commitment;
+pub use fair::FairnessError;
+pub use fair::ProvablyFairShuffler;
+pub use fair::ServerSeed;
+pub use hand_rank::HandClass;
+pub use hand_rank::HandRank;
+pub use live_hand::LiveHand;
+pub use live_hand::Street;
+pub use lookup::LookupTable;
+pub use pretty::CardBinary;
+pub use pretty::PrettyCardsExt;
+pub use pretty::PrettyStyle;
+pub use range::Combo;
+pub use range::Range;
+pub use range::RangeParseError;
 pub use model::Deal;
 pub use model::Board;
 pub use model::Hand;
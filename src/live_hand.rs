@@ -0,0 +1,303 @@
+use std::fmt;
+
+use crate::builder::DealError;
+use crate::card::Card;
+#[cfg(feature = "serde")]
+use crate::card_set::CardSet;
+use crate::deck::{Deck, DeckError};
+use crate::evaluator::{Evaluation, EVALUATOR};
+
+/// The betting rounds of a hold'em hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+impl Street {
+    pub fn next(self) -> Option<Street> {
+        match self {
+            Street::Preflop => Some(Street::Flop),
+            Street::Flop => Some(Street::Turn),
+            Street::Turn => Some(Street::River),
+            Street::River => None,
+        }
+    }
+
+    /// Number of board cards once this street is dealt.
+    pub fn board_len(self) -> usize {
+        match self {
+            Street::Preflop => 0,
+            Street::Flop => 3,
+            Street::Turn => 4,
+            Street::River => 5,
+        }
+    }
+}
+
+impl fmt::Display for Street {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Street::Preflop => "Preflop",
+            Street::Flop => "Flop",
+            Street::Turn => "Turn",
+            Street::River => "River",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A hand in progress, dealt from the top of its deck one street at a time
+/// with a burn before the flop, turn and river. Hole cards are indexed by
+/// seat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "LiveHandData"))]
+pub struct LiveHand {
+    street: Street,
+    deck: Deck,
+    hole_cards: Vec<[Card; 2]>,
+    board: Vec<Card>,
+    button: usize,
+    seed: Option<u64>,
+}
+
+/// The serialized fields of a [`LiveHand`], checked before they are trusted.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct LiveHandData {
+    street: Street,
+    deck: Deck,
+    hole_cards: Vec<[Card; 2]>,
+    board: Vec<Card>,
+    button: usize,
+    seed: Option<u64>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<LiveHandData> for LiveHand {
+    type Error = String;
+
+    fn try_from(data: LiveHandData) -> Result<Self, Self::Error> {
+        if data.board.len() != data.street.board_len() {
+            return Err(format!("{} board cards on the {}", data.board.len(), data.street));
+        }
+        // one burn before each street after the preflop
+        if data.deck.burned().len() != data.street as usize {
+            return Err(format!("{} burned cards on the {}", data.deck.burned().len(), data.street));
+        }
+        if data.hole_cards.len() < 2 {
+            return Err(format!("cannot deal to {} players", data.hole_cards.len()));
+        }
+        if data.button >= data.hole_cards.len() {
+            return Err(format!("button seat {} is not one of {} seats", data.button, data.hole_cards.len()));
+        }
+        let mut seen = CardSet::new();
+        let all_cards = data.deck.remaining().iter().chain(data.deck.burned());
+        for &card in all_cards.chain(data.hole_cards.iter().flatten()).chain(&data.board) {
+            if !seen.insert(card) {
+                return Err(format!("card {} is dealt more than once", card));
+            }
+        }
+        Ok(LiveHand {
+            street: data.street,
+            deck: data.deck,
+            hole_cards: data.hole_cards,
+            board: data.board,
+            button: data.button,
+            seed: data.seed,
+        })
+    }
+}
+
+impl LiveHand {
+    /// Deals two hole cards to each seat, one card at a time starting on the
+    /// left of the button.
+    pub fn new(mut deck: Deck, player_count: usize, button: usize, seed: Option<u64>) -> Result<LiveHand, DealError> {
+        if player_count < 2 {
+            return Err(DealError::NotEnoughPlayers(player_count));
+        }
+        if button >= player_count {
+            return Err(DealError::InvalidButton { button, player_count });
+        }
+        let first = deck.draw(player_count)?;
+        let second = deck.draw(player_count)?;
+        let hole_cards = (0..player_count)
+            .map(|seat| {
+                let i = (seat + player_count - button - 1) % player_count;
+                [first[i], second[i]]
+            })
+            .collect();
+        Ok(LiveHand {
+            street: Street::Preflop,
+            deck,
+            hole_cards,
+            board: Vec::new(),
+            button,
+            seed,
+        })
+    }
+
+    /// Burns a card and deals the next street, returning it.
+    pub fn advance(&mut self) -> Result<Street, DealError> {
+        let next = self.street.next().ok_or(DealError::HandComplete)?;
+        let count = next.board_len() - self.street.board_len();
+        if self.deck.len() < count + 1 {
+            return Err(DeckError::NotEnoughCards {
+                requested: count + 1,
+                remaining: self.deck.len(),
+            }
+            .into());
+        }
+        self.deck.burn()?;
+        self.board.extend(self.deck.draw(count)?);
+        self.street = next;
+        Ok(next)
+    }
+
+    pub fn street(&self) -> Street {
+        self.street
+    }
+
+    pub fn board(&self) -> &[Card] {
+        &self.board
+    }
+
+    pub fn hole_cards(&self) -> &[[Card; 2]] {
+        &self.hole_cards
+    }
+
+    pub fn player_count(&self) -> usize {
+        self.hole_cards.len()
+    }
+
+    pub fn burned(&self) -> &[Card] {
+        self.deck.burned()
+    }
+
+    pub fn button(&self) -> usize {
+        self.button
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// The player's best five card hand so far, `None` before the flop.
+    pub fn best_hand(&self, player: usize) -> Option<Evaluation> {
+        let hole_cards = self.hole_cards.get(player)?;
+        EVALUATOR.evaluate_best_five(hole_cards, &self.board).ok()
+    }
+
+    pub fn best_hands(&self) -> Vec<Option<Evaluation>> {
+        (0..self.player_count()).map(|player| self.best_hand(player)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{GameDealer, SeededCardShuffler};
+    use crate::hand_rank::HandClass;
+
+    fn cards(s: &str) -> Vec<Card> {
+        s.split_whitespace().map(|c| Card::new(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_streets() {
+        let deck = Deck::from_cards(cards("Ah 2c Kh 2d 3s Qh Jh Th 4s 9c 5s 8d"));
+        let mut hand = LiveHand::new(deck, 2, 1, None).unwrap();
+        assert_eq!(hand.hole_cards(), &[[cards("Ah")[0], cards("Kh")[0]], [cards("2c")[0], cards("2d")[0]]]);
+        assert_eq!(hand.street(), Street::Preflop);
+        assert_eq!(hand.best_hands(), vec![None, None]);
+
+        assert_eq!(hand.advance(), Ok(Street::Flop));
+        assert_eq!(hand.board(), cards("Qh Jh Th").as_slice());
+        assert_eq!(hand.best_hand(0).unwrap().rank.class(), HandClass::StraightFlush);
+        assert_eq!(hand.best_hand(1).unwrap().rank.class(), HandClass::Pair);
+
+        assert_eq!(hand.advance(), Ok(Street::Turn));
+        assert_eq!(hand.advance(), Ok(Street::River));
+        assert_eq!(hand.board(), cards("Qh Jh Th 9c 8d").as_slice());
+        assert_eq!(hand.burned(), cards("3s 4s 5s").as_slice());
+        assert_eq!(hand.advance(), Err(DealError::HandComplete));
+        assert_eq!(hand.best_hand(2), None);
+
+        let deck = Deck::from_cards(cards("Ah 2c Kh 2d 3s Qh Jh"));
+        let mut short = LiveHand::new(deck, 2, 0, None).unwrap();
+        assert_eq!(
+            short.advance(),
+            Err(DealError::Deck(DeckError::NotEnoughCards {
+                requested: 4,
+                remaining: 3
+            }))
+        );
+        assert!(short.burned().is_empty());
+    }
+
+    #[test]
+    fn test_deal_left_of_button() {
+        let deck = || Deck::from_cards(cards("2c 3c 4c 5c 6c 7c 8c 9c"));
+        let hand = LiveHand::new(deck(), 4, 2, None).unwrap();
+        let seats: Vec<_> = hand.hole_cards().iter().map(|cards| cards[0].to_string()).collect();
+        assert_eq!(seats, vec!["3c", "4c", "5c", "2c"]);
+        assert_eq!(hand.hole_cards()[3][1], cards("6c")[0]);
+        assert_eq!(
+            LiveHand::new(deck(), 4, 4, None),
+            Err(DealError::InvalidButton {
+                button: 4,
+                player_count: 4
+            })
+        );
+        assert_eq!(LiveHand::new(deck(), 0, 0, None), Err(DealError::NotEnoughPlayers(0)));
+        assert_eq!(LiveHand::new(deck(), 1, 0, None), Err(DealError::NotEnoughPlayers(1)));
+    }
+
+    #[test]
+    fn test_start_hand() {
+        let mut hand = GameDealer::new(SeededCardShuffler::new(5)).start_hand(9).unwrap();
+        assert_eq!(hand.seed(), Some(5));
+        assert_eq!(hand.player_count(), 9);
+        while hand.advance().is_ok() {}
+        assert_eq!(hand.street(), Street::River);
+        assert!(hand.best_hands().iter().all(Option::is_some));
+
+        let too_many = GameDealer::new(SeededCardShuffler::new(5)).start_hand(27);
+        assert!(matches!(too_many, Err(DealError::Deck(_))));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_mid_hand() {
+        let mut hand = GameDealer::new(SeededCardShuffler::new(5)).start_hand(3).unwrap();
+        hand.advance().unwrap();
+        let json = serde_json::to_string(&hand).unwrap();
+        assert!(json.contains(&format!("\"{}\"", hand.board()[0])));
+        let mut restored: LiveHand = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, hand);
+        assert_eq!(restored.advance(), hand.advance());
+        assert_eq!(restored, hand);
+
+        let invalid = |edit: &dyn Fn(&mut serde_json::Value)| {
+            let mut value = serde_json::to_value(&hand).unwrap();
+            edit(&mut value);
+            serde_json::from_value::<LiveHand>(value).is_err()
+        };
+        assert!(!invalid(&|_| {}));
+        assert!(invalid(&|v| v["street"] = "Flop".into()));
+        assert!(invalid(&|v| v["button"] = 3.into()));
+        assert!(invalid(&|v| v["board"][0] = v["hole_cards"][0][0].clone()));
+        assert!(invalid(&|v| {
+            let burned = v["deck"]["burned"].as_array_mut().unwrap();
+            burned.pop();
+        }));
+        assert!(invalid(&|v| {
+            let card = v["deck"]["cards"].as_array_mut().unwrap().remove(0);
+            v["deck"]["burned"].as_array_mut().unwrap().push(card);
+        }));
+    }
+}
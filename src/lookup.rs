@@ -1,15 +1,23 @@
-use itertools::Itertools;
-use std::collections::HashMap;
-
 use crate::card::Card;
 
+/// Lookup tables mapping every 5 card hand to its rank in [1, 7462]. The
+/// tables are built by `const fn` so that `LOOKUP_TABLE` is computed at
+/// compile time.
+///
+/// Hands with five distinct ranks are indexed directly by their 13 bit
+/// rankbits: `flush_lookup` when all suits match, `unique_lookup` otherwise.
+/// Hands with repeated ranks are found by binary search of their prime
+/// product in `multiples_products`, whose rank sits at the same index in
+/// `multiples_ranks`.
 pub struct LookupTable {
-    pub flush_lookup: HashMap<u32, u32>,
-    pub unsuited_lookup: HashMap<u32, u32>,
-    pub max_to_rank_class_lookup: HashMap<u32, u32>,
-    pub rank_class_to_string_lookup: HashMap<u32, &'static str>,
+    pub flush_lookup: [u16; LookupTable::RANKBITS_SIZE],
+    pub unique_lookup: [u16; LookupTable::RANKBITS_SIZE],
+    pub multiples_products: [u32; LookupTable::MULTIPLES_SIZE],
+    pub multiples_ranks: [u16; LookupTable::MULTIPLES_SIZE],
 }
 
+pub static LOOKUP_TABLE: LookupTable = LookupTable::new();
+
 impl LookupTable {
     pub const MAX_STRAIGHT_FLUSH: u32 = 10;
     pub const MAX_FOUR_OF_A_KIND: u32 = 166;
@@ -21,213 +29,277 @@ impl LookupTable {
     pub const MAX_PAIR: u32 = 6185;
     pub const MAX_HIGH_CARD: u32 = 7462;
 
-    pub fn new() -> Self {
+    pub const MAX_TO_RANK_CLASS: [(u32, u32); 9] = [
+        (Self::MAX_STRAIGHT_FLUSH, 1),
+        (Self::MAX_FOUR_OF_A_KIND, 2),
+        (Self::MAX_FULL_HOUSE, 3),
+        (Self::MAX_FLUSH, 4),
+        (Self::MAX_STRAIGHT, 5),
+        (Self::MAX_THREE_OF_A_KIND, 6),
+        (Self::MAX_TWO_PAIR, 7),
+        (Self::MAX_PAIR, 8),
+        (Self::MAX_HIGH_CARD, 9),
+    ];
+
+    pub const RANK_CLASS_TO_STRING: [(u32, &'static str); 9] = [
+        (1, "Straight Flush"),
+        (2, "Four of a Kind"),
+        (3, "Full House"),
+        (4, "Flush"),
+        (5, "Straight"),
+        (6, "Three of a Kind"),
+        (7, "Two Pair"),
+        (8, "Pair"),
+        (9, "High Card"),
+    ];
+
+    pub const RANKBITS_SIZE: usize = 1 << 13;
+    // quads, full houses, trips, two pairs and pairs
+    pub const MULTIPLES_SIZE: usize = 156 + 156 + 858 + 858 + 2860;
+
+    const STRAIGHTS: [u32; 10] = [
+        7936, // 0b1111100000000, // royal flush
+        3968, // 0b111110000000,
+        1984, // 0b11111000000,
+        992,  // 0b1111100000,
+        496,  // 0b111110000,
+        248,  // 0b11111000,
+        124,  // 0b1111100,
+        62,   // 0b111110,
+        31,   // 0b11111,
+        4111, // 0b1000000001111, // 5 high
+    ];
+
+    pub const fn new() -> Self {
         let mut lookup_table = LookupTable {
-            flush_lookup: HashMap::new(),
-            unsuited_lookup: HashMap::new(),
-            max_to_rank_class_lookup: HashMap::new(),
-            rank_class_to_string_lookup: HashMap::new(),
+            flush_lookup: [0; Self::RANKBITS_SIZE],
+            unique_lookup: [0; Self::RANKBITS_SIZE],
+            multiples_products: [0; Self::MULTIPLES_SIZE],
+            multiples_ranks: [0; Self::MULTIPLES_SIZE],
         };
 
         lookup_table.flushes();
         lookup_table.multiples();
-        lookup_table.max_to_rank_class();
-        lookup_table.rank_class_to_string();
+        lookup_table.sort_multiples();
 
         lookup_table
     }
 
-    pub fn rank_class_to_string(&mut self) {
-        self.rank_class_to_string_lookup.insert(1, "Straight Flush");
-        self.rank_class_to_string_lookup.insert(2, "Four of a Kind");
-        self.rank_class_to_string_lookup.insert(3, "Full House");
-        self.rank_class_to_string_lookup.insert(4, "Flush");
-        self.rank_class_to_string_lookup.insert(5, "Straight");
-        self.rank_class_to_string_lookup
-            .insert(6, "Three of a Kind");
-        self.rank_class_to_string_lookup.insert(7, "Two Pair");
-        self.rank_class_to_string_lookup.insert(8, "Pair");
-        self.rank_class_to_string_lookup.insert(9, "High Card");
+    /// Rank of a hand with repeated ranks given its prime product.
+    pub fn multiples_rank(&self, prime_product: u32) -> Option<u32> {
+        self.multiples_products
+            .binary_search(&prime_product)
+            .ok()
+            .map(|i| self.multiples_ranks[i] as u32)
     }
 
-    fn get_lexographically_next_bit_sequence(&self, bits: i32) -> impl Iterator<Item = i32> {
-        let mut next = (bits | (bits - 1)) + 1;
-        next |= (((next & -next) / (bits & -bits)) >> 1_i32)
-            - (1_i32);
-
-        std::iter::successors(Some(next), |&next| {
-            let t = (next | (next - 1)) + 1;
-            Some(t | ((((t & -t) / (next & -next)) >> 1) - 1))
-        })
+    const fn get_lexographically_next_bit_sequence(bits: i32) -> i32 {
+        let next = (bits | (bits - 1)) + 1;
+        next | ((((next & -next) / (bits & -bits)) >> 1) - 1)
     }
 
-    pub fn flushes(&mut self) {
-        let straight_flushes = vec![
-            7936, // 0b1111100000000, // royal flush
-            3968, // 0b111110000000,
-            1984, // 0b11111000000,
-            992,  // 0b1111100000,
-            496,  // 0b111110000,
-            248,  // 0b11111000,
-            124,  // 0b1111100,
-            62,   // 0b111110,
-            31,   // 0b11111,
-            4111, // 0b1000000001111, // 5 high
-        ];
-
-        let mut flushes = Vec::new();
-        let mut gen = self.get_lexographically_next_bit_sequence(0b11111);
-
-        for _ in 0..(1277 + straight_flushes.len() - 1) {
-            let f = gen.next().unwrap();
-
-            if !straight_flushes.contains(&f) {
-                flushes.push(f);
+    const fn is_straight(bits: u32) -> bool {
+        let mut i = 0;
+        while i < Self::STRAIGHTS.len() {
+            if Self::STRAIGHTS[i] == bits {
+                return true;
             }
+            i += 1;
         }
+        false
+    }
 
-        flushes.reverse();
-
-        let mut rank = 1;
-        for sf in &straight_flushes {
-            let prime_product = Card::prime_product_from_rankbits(*sf as u32);
-            self.flush_lookup.insert(prime_product, rank);
-            rank += 1;
+    const fn flushes(&mut self) {
+        let mut flushes = [0u32; 1277];
+        let mut count = 0;
+        let mut bits = 0b11111;
+
+        let mut i = 0;
+        while i < 1277 + Self::STRAIGHTS.len() - 1 {
+            bits = Self::get_lexographically_next_bit_sequence(bits);
+            if !Self::is_straight(bits as u32) {
+                // generated from worst to best, so store reversed
+                flushes[1276 - count] = bits as u32;
+                count += 1;
+            }
+            i += 1;
         }
 
-        rank = LookupTable::MAX_FULL_HOUSE + 1;
-        for f in &flushes {
-            let prime_product = Card::prime_product_from_rankbits(*f as u32);
-            self.flush_lookup.insert(prime_product, rank);
-            rank += 1;
+        let mut i = 0;
+        while i < Self::STRAIGHTS.len() {
+            self.flush_lookup[Self::STRAIGHTS[i] as usize] = (i + 1) as u16;
+            i += 1;
         }
 
-        // for (i, f) in flushes.iter().enumerate() {
-        //     self.flush_lookup.insert(*f, (i + 1) as u32);
-        // }
+        let mut i = 0;
+        while i < flushes.len() {
+            self.flush_lookup[flushes[i] as usize] = (Self::MAX_FULL_HOUSE as usize + 1 + i) as u16;
+            i += 1;
+        }
 
-        self.straight_and_highcards(straight_flushes, flushes)
+        self.straight_and_highcards(&flushes)
     }
 
-    pub fn straight_and_highcards(&mut self, straights: Vec<i32>, highcards: Vec<i32>) {
-        let mut rank = LookupTable::MAX_FLUSH + 1;
-
-        for s in straights {
-            let prime_product = Card::prime_product_from_rankbits(s as u32);
-            self.unsuited_lookup.insert(prime_product, rank);
-            rank += 1;
+    const fn straight_and_highcards(&mut self, highcards: &[u32; 1277]) {
+        let mut i = 0;
+        while i < Self::STRAIGHTS.len() {
+            self.unique_lookup[Self::STRAIGHTS[i] as usize] = (Self::MAX_FLUSH as usize + 1 + i) as u16;
+            i += 1;
         }
 
-        rank = LookupTable::MAX_PAIR + 1;
-        for h in highcards {
-            let prime_product = Card::prime_product_from_rankbits(h as u32);
-            self.unsuited_lookup.insert(prime_product, rank);
-            rank += 1;
+        let mut i = 0;
+        while i < highcards.len() {
+            self.unique_lookup[highcards[i] as usize] = (Self::MAX_PAIR as usize + 1 + i) as u16;
+            i += 1;
         }
     }
 
-    pub fn multiples(&mut self) {
-        let backwards_ranks: Vec<_> = (0..Card::INT_RANKS.len()).rev().collect();
-
-        // 1) Four of a Kind
-        let mut rank = LookupTable::MAX_STRAIGHT_FLUSH + 1;
-
-        // for each choice of a set of four rank
-        for i in &backwards_ranks {
-            // and for each possible kicker rank
-            let mut kickers: Vec<_> = backwards_ranks.clone();
-            kickers.retain(|&x| x != *i);
-            for k in &kickers {
-                let product = Card::PRIMES[*i].pow(4) * Card::PRIMES[*k];
-                self.unsuited_lookup.insert(product, rank);
-                rank += 1;
+    const fn multiples(&mut self) {
+        let primes = Card::PRIMES;
+        let mut n = 0;
+
+        // 1) Four of a Kind, for each rank of four and each kicker from aces down
+        let mut rank = Self::MAX_STRAIGHT_FLUSH + 1;
+        let mut i = 13;
+        while i > 0 {
+            i -= 1;
+            let mut k = 13;
+            while k > 0 {
+                k -= 1;
+                if k != i {
+                    self.push_multiple(n, primes[i].pow(4) * primes[k], rank);
+                    n += 1;
+                    rank += 1;
+                }
             }
         }
 
-        // 2) Full House
-        let mut rank = LookupTable::MAX_FOUR_OF_A_KIND + 1;
-
-        // for each three of a kind
-        for i in &backwards_ranks {
-            // and for each choice of pair rank
-            let mut pairranks: Vec<_> = backwards_ranks.clone();
-            pairranks.retain(|&x| x != *i);
-            for pr in &pairranks {
-                let product = Card::PRIMES[*i].pow(3) * Card::PRIMES[*pr].pow(2);
-                self.unsuited_lookup.insert(product, rank);
-                rank += 1;
+        // 2) Full House, for each three of a kind and each pair rank
+        let mut rank = Self::MAX_FOUR_OF_A_KIND + 1;
+        let mut i = 13;
+        while i > 0 {
+            i -= 1;
+            let mut pr = 13;
+            while pr > 0 {
+                pr -= 1;
+                if pr != i {
+                    self.push_multiple(n, primes[i].pow(3) * primes[pr].pow(2), rank);
+                    n += 1;
+                    rank += 1;
+                }
             }
         }
 
-        // 3) Three of a Kind
-        rank = LookupTable::MAX_STRAIGHT + 1;
-
-        // pick three of one rank
-        for r in &backwards_ranks {
-            let mut kickers: Vec<_> = backwards_ranks.clone();
-            kickers.retain(|&x| x != *r);
-            let gen: Vec<_> = kickers.iter().combinations(2).collect();
+        // 3) Three of a Kind, pick three of one rank and two distinct kickers
+        let mut rank = Self::MAX_STRAIGHT + 1;
+        let mut r = 13;
+        while r > 0 {
+            r -= 1;
+            let mut c1 = 13;
+            while c1 > 0 {
+                c1 -= 1;
+                let mut c2 = c1;
+                while c2 > 0 {
+                    c2 -= 1;
+                    if c1 != r && c2 != r {
+                        self.push_multiple(n, primes[r].pow(3) * primes[c1] * primes[c2], rank);
+                        n += 1;
+                        rank += 1;
+                    }
+                }
+            }
+        }
 
-            for kickers in gen {
-                let (c1, c2) = (kickers[0], kickers[1]);
-                let product = Card::PRIMES[*r].pow(3) * Card::PRIMES[*c1] * Card::PRIMES[*c2];
-                self.unsuited_lookup.insert(product, rank);
-                rank += 1;
+        // 4) Two Pair, for each pair of pairs and each remaining kicker
+        let mut rank = Self::MAX_THREE_OF_A_KIND + 1;
+        let mut pair1 = 13;
+        while pair1 > 0 {
+            pair1 -= 1;
+            let mut pair2 = pair1;
+            while pair2 > 0 {
+                pair2 -= 1;
+                let mut kicker = 13;
+                while kicker > 0 {
+                    kicker -= 1;
+                    if kicker != pair1 && kicker != pair2 {
+                        let product = primes[pair1].pow(2) * primes[pair2].pow(2) * primes[kicker];
+                        self.push_multiple(n, product, rank);
+                        n += 1;
+                        rank += 1;
+                    }
+                }
             }
         }
 
-        // 4) Two Pair
-        let mut rank = LookupTable::MAX_THREE_OF_A_KIND + 1;
-
-        let tpgen: Vec<_> = backwards_ranks.iter().combinations(2).collect();
-        for tp in tpgen {
-            let (pair1, pair2) = (*tp[0], *tp[1]);
-            let mut kickers: Vec<_> = backwards_ranks.clone();
-            kickers.retain(|&x| x != pair1 && x != pair2);
-            for kicker in &kickers {
-                let product =
-                    Card::PRIMES[pair1].pow(2) * Card::PRIMES[pair2].pow(2) * Card::PRIMES[*kicker];
-                self.unsuited_lookup.insert(product, rank);
-                rank += 1;
+        // 5) Pair, choose a pair and three distinct kickers
+        let mut rank = Self::MAX_TWO_PAIR + 1;
+        let mut pairrank = 13;
+        while pairrank > 0 {
+            pairrank -= 1;
+            let mut k1 = 13;
+            while k1 > 0 {
+                k1 -= 1;
+                let mut k2 = k1;
+                while k2 > 0 {
+                    k2 -= 1;
+                    let mut k3 = k2;
+                    while k3 > 0 {
+                        k3 -= 1;
+                        if k1 != pairrank && k2 != pairrank && k3 != pairrank {
+                            let product = primes[pairrank].pow(2) * primes[k1] * primes[k2] * primes[k3];
+                            self.push_multiple(n, product, rank);
+                            n += 1;
+                            rank += 1;
+                        }
+                    }
+                }
             }
         }
+    }
+
+    const fn push_multiple(&mut self, index: usize, product: u32, rank: u32) {
+        self.multiples_products[index] = product;
+        self.multiples_ranks[index] = rank as u16;
+    }
 
-        // 5) Pair
-        let mut rank = LookupTable::MAX_TWO_PAIR + 1;
-
-        // choose a pair
-        for pairrank in &backwards_ranks {
-            let mut kickers: Vec<_> = backwards_ranks.clone();
-            kickers.retain(|&x| x != *pairrank);
-            let kgen: Vec<_> = kickers.iter().combinations(3).collect();
-
-            for kickers in kgen {
-                let (k1, k2, k3) = (*kickers[0], *kickers[1], *kickers[2]);
-                let product = Card::PRIMES[*pairrank].pow(2)
-                    * Card::PRIMES[k1]
-                    * Card::PRIMES[k2]
-                    * Card::PRIMES[k3];
-                self.unsuited_lookup.insert(product, rank);
-                rank += 1;
+    /// Bottom-up merge sort of the multiples by prime product, so that they
+    /// can be binary searched.
+    const fn sort_multiples(&mut self) {
+        let mut products_buf = [0u32; Self::MULTIPLES_SIZE];
+        let mut ranks_buf = [0u16; Self::MULTIPLES_SIZE];
+        let len = Self::MULTIPLES_SIZE;
+
+        let mut width = 1;
+        while width < len {
+            let mut start = 0;
+            while start < len {
+                let mid = if start + width < len { start + width } else { len };
+                let end = if start + 2 * width < len { start + 2 * width } else { len };
+                let (mut a, mut b, mut out) = (start, mid, start);
+                while out < end {
+                    if b >= end || (a < mid && self.multiples_products[a] <= self.multiples_products[b]) {
+                        products_buf[out] = self.multiples_products[a];
+                        ranks_buf[out] = self.multiples_ranks[a];
+                        a += 1;
+                    } else {
+                        products_buf[out] = self.multiples_products[b];
+                        ranks_buf[out] = self.multiples_ranks[b];
+                        b += 1;
+                    }
+                    out += 1;
+                }
+                start += 2 * width;
             }
+            self.multiples_products = products_buf;
+            self.multiples_ranks = ranks_buf;
+            width *= 2;
         }
     }
+}
 
-    pub fn max_to_rank_class(&mut self) {
-        //let mut max_to_rank_class = HashMap::new();
-        self.max_to_rank_class_lookup
-            .insert(Self::MAX_STRAIGHT_FLUSH, 1);
-        self.max_to_rank_class_lookup
-            .insert(Self::MAX_FOUR_OF_A_KIND, 2);
-        self.max_to_rank_class_lookup
-            .insert(Self::MAX_FULL_HOUSE, 3);
-        self.max_to_rank_class_lookup.insert(Self::MAX_FLUSH, 4);
-        self.max_to_rank_class_lookup.insert(Self::MAX_STRAIGHT, 5);
-        self.max_to_rank_class_lookup
-            .insert(Self::MAX_THREE_OF_A_KIND, 6);
-        self.max_to_rank_class_lookup.insert(Self::MAX_TWO_PAIR, 7);
-        self.max_to_rank_class_lookup.insert(Self::MAX_PAIR, 8);
-        self.max_to_rank_class_lookup.insert(Self::MAX_HIGH_CARD, 9);
+impl Default for LookupTable {
+    fn default() -> Self {
+        LookupTable::new()
     }
 }
 
@@ -237,17 +309,27 @@ mod tests {
 
     #[test]
     fn test_new() {
-        let lookup_table = LookupTable::new();
-        assert_eq!(lookup_table.flush_lookup.len(), 1287);
-        assert_eq!(lookup_table.unsuited_lookup.len(), 6175);
+        let lookup_table = &LOOKUP_TABLE;
+        let flushes = lookup_table.flush_lookup.iter().filter(|&&r| r != 0).count();
+        let uniques = lookup_table.unique_lookup.iter().filter(|&&r| r != 0).count();
+        assert_eq!(flushes, 1287);
+        assert_eq!(uniques + lookup_table.multiples_products.len(), 6175);
+    }
+
+    #[test]
+    fn test_multiples_sorted() {
+        let products = &LOOKUP_TABLE.multiples_products;
+        assert!(products.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(LOOKUP_TABLE.multiples_rank(41u32.pow(4) * 37), Some(11));
+        assert_eq!(LOOKUP_TABLE.multiples_rank(3u32.pow(2) * 2u32.pow(2) * 5), Some(LookupTable::MAX_TWO_PAIR));
+        assert_eq!(LOOKUP_TABLE.multiples_rank(2 * 3 * 5 * 7 * 11), None);
     }
 
     #[test]
     fn test_get_lexographically_next_bit_sequence() {
-        let lookup = LookupTable::new();
         let bits = 0b11111;
-        let mut sequence = lookup.get_lexographically_next_bit_sequence(bits);
-        assert_eq!(sequence.next(), Some(47));
-        assert_eq!(sequence.next(), Some(55));
+        let next = LookupTable::get_lexographically_next_bit_sequence(bits);
+        assert_eq!(next, 47);
+        assert_eq!(LookupTable::get_lexographically_next_bit_sequence(next), 55);
     }
 }
@@ -1,3 +1,5 @@
+use crate::hand_rank::HandRank;
+
 #[derive(Debug, PartialEq)]
 pub struct PlayerHand {
     pub hand: Vec<String>,
@@ -8,6 +10,7 @@ pub struct Hand {
     pub cards: Vec<String>,
     pub score: f64,
     pub description: String,
+    pub rank: HandRank,
 }
 #[derive(Debug, PartialEq)]
 pub struct Board {
@@ -20,4 +23,36 @@ pub struct Board {
 pub struct Deal {
     pub board: Board,
     pub hands: Vec<Hand>,
+    /// Player indices grouped by equal hand rank, best hands first.
+    pub standings: Vec<Vec<usize>>,
+    /// Seat of the dealer button, the first card going to its left.
+    pub button: usize,
+    /// Seed of the shuffle, when the shuffler can replay it.
+    pub seed: Option<u64>,
+}
+
+impl Deal {
+    /// Groups player indices by hand rank, best first, keeping ties together.
+    pub fn standings_of(hands: &[Hand]) -> Vec<Vec<usize>> {
+        let mut order: Vec<usize> = (0..hands.len()).collect();
+        order.sort_by(|&a, &b| hands[b].rank.cmp(&hands[a].rank).then(a.cmp(&b)));
+        let mut standings: Vec<Vec<usize>> = Vec::new();
+        for i in order {
+            match standings.last_mut() {
+                Some(group) if hands[group[0]].rank == hands[i].rank => group.push(i),
+                _ => standings.push(vec![i]),
+            }
+        }
+        standings
+    }
+
+    /// Indices of the players holding the best hand.
+    pub fn winners(&self) -> &[usize] {
+        self.standings.first().map_or(&[], Vec::as_slice)
+    }
+
+    /// Whether the pot is split between several winners.
+    pub fn is_split_pot(&self) -> bool {
+        self.winners().len() > 1
+    }
 }
\ No newline at end of file
@@ -0,0 +1,221 @@
+use std::fmt;
+
+use crate::card::{Card, Suit};
+
+const RESET: &str = "\x1b[0m";
+const RED: &str = "\x1b[31m";
+const BLUE: &str = "\x1b[34m";
+const GREEN: &str = "\x1b[32m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyStyle {
+    pub symbols: bool,
+    pub color: bool,
+    pub four_color: bool,
+}
+
+impl PrettyStyle {
+    pub const PLAIN: PrettyStyle = PrettyStyle {
+        symbols: false,
+        color: false,
+        four_color: false,
+    };
+    pub const UNICODE: PrettyStyle = PrettyStyle {
+        symbols: true,
+        color: false,
+        four_color: false,
+    };
+    pub const COLOR: PrettyStyle = PrettyStyle {
+        symbols: true,
+        color: true,
+        four_color: false,
+    };
+    pub const FOUR_COLOR: PrettyStyle = PrettyStyle {
+        symbols: true,
+        color: true,
+        four_color: true,
+    };
+
+    fn suit_symbol(&self, suit: Suit) -> char {
+        if !self.symbols {
+            return suit.to_char();
+        }
+        match suit {
+            Suit::Spades => '\u{2660}',
+            Suit::Hearts => '\u{2764}',
+            Suit::Diamonds => '\u{2666}',
+            Suit::Clubs => '\u{2663}',
+        }
+    }
+
+    fn color_code(&self, suit: Suit) -> Option<&'static str> {
+        if !self.color {
+            return None;
+        }
+        match (suit, self.four_color) {
+            (Suit::Hearts, _) | (Suit::Diamonds, false) => Some(RED),
+            (Suit::Diamonds, true) => Some(BLUE),
+            (Suit::Clubs, true) => Some(GREEN),
+            _ => None,
+        }
+    }
+}
+
+impl Default for PrettyStyle {
+    fn default() -> Self {
+        PrettyStyle::UNICODE
+    }
+}
+
+/// Display adapter rendering a single card as "[ A ♠ ]".
+#[derive(Debug, Clone, Copy)]
+pub struct PrettyCard {
+    card: Card,
+    style: PrettyStyle,
+}
+
+impl fmt::Display for PrettyCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rank = self.card.rank().to_char();
+        let suit = self.style.suit_symbol(self.card.suit());
+        match self.style.color_code(self.card.suit()) {
+            Some(code) => write!(f, "[ {}{} {}{} ]", code, rank, suit, RESET),
+            None => write!(f, "[ {} {} ]", rank, suit),
+        }
+    }
+}
+
+/// Display adapter rendering a slice of cards separated by spaces.
+#[derive(Debug, Clone, Copy)]
+pub struct PrettyCards<'a> {
+    cards: &'a [Card],
+    style: PrettyStyle,
+}
+
+impl fmt::Display for PrettyCards<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, card) in self.cards.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", card.pretty(self.style))?;
+        }
+        Ok(())
+    }
+}
+
+/// Display adapter showing the bit layout of a card integer, one byte per field:
+/// `xxxbbbbb bbbbbbbb cdhsrrrr xxpppppp`. The alternate form (`{:#}`) prints the
+/// field legend above the bits.
+#[derive(Debug, Clone, Copy)]
+pub struct CardBinary(pub u32);
+
+impl CardBinary {
+    pub const LEGEND: &'static str = "xxxbbbbb bbbbbbbb cdhsrrrr xxpppppp";
+}
+
+impl fmt::Display for CardBinary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            writeln!(f, "{}", CardBinary::LEGEND)?;
+        }
+        let bytes = self.0.to_be_bytes();
+        write!(
+            f,
+            "{:08b} {:08b} {:08b} {:08b}",
+            bytes[0], bytes[1], bytes[2], bytes[3]
+        )
+    }
+}
+
+pub trait PrettyCardsExt {
+    fn pretty(&self, style: PrettyStyle) -> PrettyCards<'_>;
+}
+
+impl PrettyCardsExt for [Card] {
+    fn pretty(&self, style: PrettyStyle) -> PrettyCards<'_> {
+        PrettyCards { cards: self, style }
+    }
+}
+
+impl Card {
+    pub fn pretty(&self, style: PrettyStyle) -> PrettyCard {
+        PrettyCard { card: *self, style }
+    }
+
+    pub fn int_to_binary(card_int: u32) -> String {
+        CardBinary(card_int).to_string()
+    }
+
+    pub fn int_to_pretty_str(card_int: u32) -> String {
+        format!(" {} ", Card(card_int).pretty(PrettyStyle::default()))
+    }
+
+    pub fn print_pretty_card(card_int: u32) {
+        println!("{}", Card::int_to_pretty_str(card_int));
+    }
+
+    pub fn print_pretty_cards(card_ints: &[u32]) {
+        let cards: Vec<Card> = card_ints.iter().map(|&c| Card(c)).collect();
+        println!("{}", cards.pretty(PrettyStyle::default()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pretty_card() {
+        let ace = Card::new("As").unwrap();
+        let heart = Card::new("Th").unwrap();
+        assert_eq!(ace.pretty(PrettyStyle::PLAIN).to_string(), "[ A s ]");
+        assert_eq!(ace.pretty(PrettyStyle::UNICODE).to_string(), "[ A \u{2660} ]");
+        assert_eq!(ace.pretty(PrettyStyle::COLOR).to_string(), "[ A \u{2660} ]");
+        assert_eq!(
+            heart.pretty(PrettyStyle::COLOR).to_string(),
+            "[ \x1b[31mT \u{2764}\x1b[0m ]"
+        );
+        assert_eq!(Card::int_to_pretty_str(ace.0), " [ A \u{2660} ] ");
+    }
+
+    #[test]
+    fn test_four_color() {
+        let diamond = Card::new("2d").unwrap();
+        let club = Card::new("2c").unwrap();
+        assert_eq!(
+            diamond.pretty(PrettyStyle::COLOR).to_string(),
+            "[ \x1b[31m2 \u{2666}\x1b[0m ]"
+        );
+        assert_eq!(
+            diamond.pretty(PrettyStyle::FOUR_COLOR).to_string(),
+            "[ \x1b[34m2 \u{2666}\x1b[0m ]"
+        );
+        assert_eq!(
+            club.pretty(PrettyStyle::FOUR_COLOR).to_string(),
+            "[ \x1b[32m2 \u{2663}\x1b[0m ]"
+        );
+    }
+
+    #[test]
+    fn test_int_to_binary() {
+        let king = Card::new("Kd").unwrap();
+        assert_eq!(
+            Card::int_to_binary(king.0),
+            "00001000 00000000 01001011 00100101"
+        );
+        assert_eq!(
+            format!("{:#}", CardBinary(king.0)),
+            "xxxbbbbb bbbbbbbb cdhsrrrr xxpppppp\n00001000 00000000 01001011 00100101"
+        );
+    }
+
+    #[test]
+    fn test_pretty_cards() {
+        let cards = [Card::new("Ah").unwrap(), Card::new("Kc").unwrap()];
+        assert_eq!(
+            cards.pretty(PrettyStyle::PLAIN).to_string(),
+            "[ A h ] [ K c ]"
+        );
+    }
+}
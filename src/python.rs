@@ -0,0 +1,273 @@
+//! Python bindings mirroring the deuces API, so that scripts written against
+//! deuces only need to change their imports:
+//!
+//! ```python
+//! from deuces_rs import Card, Deck, Evaluator
+//!
+//! board = [Card.new("Ah"), Card.new("Kd"), Card.new("Jc")]
+//! hand = [Card.new("Qs"), Card.new("Th")]
+//! evaluator = Evaluator()
+//! rank = evaluator.evaluate(hand, board)
+//! print(evaluator.class_to_string(evaluator.get_rank_class(rank)))
+//! ```
+
+// triggered by the code generated for `PyResult` returning methods
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::builder::{DealError, Dealer, GameDealer, SeededCardShuffler};
+use crate::card::{Card, CardParseError};
+use crate::deck::{Deck, DeckError};
+use crate::evaluator::{EvaluationError, EVALUATOR};
+use crate::hand_rank::{HandClass, HandRank};
+use crate::lookup::LookupTable;
+
+impl From<CardParseError> for PyErr {
+    fn from(e: CardParseError) -> Self {
+        PyValueError::new_err(e.to_string())
+    }
+}
+
+impl From<EvaluationError> for PyErr {
+    fn from(e: EvaluationError) -> Self {
+        PyValueError::new_err(e.to_string())
+    }
+}
+
+impl From<DeckError> for PyErr {
+    fn from(e: DeckError) -> Self {
+        PyValueError::new_err(e.to_string())
+    }
+}
+
+impl From<DealError> for PyErr {
+    fn from(e: DealError) -> Self {
+        PyValueError::new_err(e.to_string())
+    }
+}
+
+fn hand_rank(hr: u32) -> PyResult<HandRank> {
+    HandRank::new(hr).ok_or_else(|| PyValueError::new_err("Invalid hand rank, cannot return rank class"))
+}
+
+fn card(card_int: u32) -> PyResult<Card> {
+    Card::from_int(card_int).ok_or_else(|| PyValueError::new_err(format!("{} is not a valid card", card_int)))
+}
+
+/// Static class of card helpers, cards being plain ints as in deuces.
+#[pyclass(name = "Card")]
+struct PyCard;
+
+#[pymethods]
+impl PyCard {
+    #[staticmethod]
+    #[allow(clippy::new_ret_no_self)]
+    fn new(string: &str) -> PyResult<u32> {
+        Ok(Card::new(string)?.0)
+    }
+
+    #[staticmethod]
+    fn int_to_str(card_int: u32) -> PyResult<String> {
+        Ok(Card::int_to_str(card(card_int)?.0))
+    }
+
+    #[staticmethod]
+    fn get_rank_int(card_int: u32) -> u32 {
+        Card::get_rank_int(card_int)
+    }
+
+    #[staticmethod]
+    fn get_suit_int(card_int: u32) -> u32 {
+        Card::get_suit_int(card_int)
+    }
+
+    #[staticmethod]
+    fn get_bitrank_int(card_int: u32) -> u32 {
+        Card::get_bitrank_int(card_int)
+    }
+
+    #[staticmethod]
+    fn get_prime(card_int: u32) -> u32 {
+        Card::get_prime(card_int)
+    }
+
+    #[staticmethod]
+    fn hand_to_binary(card_strs: Vec<String>) -> PyResult<Vec<u32>> {
+        let strs: Vec<&str> = card_strs.iter().map(String::as_str).collect();
+        Ok(Card::hand_to_binary(&strs)?)
+    }
+
+    #[staticmethod]
+    fn prime_product_from_hand(card_ints: Vec<u32>) -> u32 {
+        Card::prime_product_from_hand(&card_ints)
+    }
+
+    #[staticmethod]
+    fn prime_product_from_rankbits(rankbits: u32) -> u32 {
+        Card::prime_product_from_rankbits(rankbits)
+    }
+
+    #[staticmethod]
+    fn int_to_binary(card_int: u32) -> String {
+        Card::int_to_binary(card_int)
+    }
+
+    #[staticmethod]
+    fn int_to_pretty_str(card_int: u32) -> PyResult<String> {
+        Ok(Card::int_to_pretty_str(card(card_int)?.0))
+    }
+
+    #[staticmethod]
+    fn print_pretty_card(card_int: u32) -> PyResult<()> {
+        Card::print_pretty_card(card(card_int)?.0);
+        Ok(())
+    }
+
+    #[staticmethod]
+    fn print_pretty_cards(card_ints: Vec<u32>) -> PyResult<()> {
+        for &card_int in &card_ints {
+            card(card_int)?;
+        }
+        Card::print_pretty_cards(&card_ints);
+        Ok(())
+    }
+}
+
+#[pyclass(name = "Evaluator")]
+struct PyEvaluator;
+
+#[pymethods]
+impl PyEvaluator {
+    #[new]
+    fn py_new() -> Self {
+        PyEvaluator
+    }
+
+    fn evaluate(&self, cards: Vec<u32>, board: Vec<u32>) -> PyResult<u32> {
+        Ok(EVALUATOR.evaluate(&cards, &board)?.value())
+    }
+
+    fn get_rank_class(&self, hr: u32) -> PyResult<u32> {
+        Ok(EVALUATOR.get_rank_class(hand_rank(hr)?).to_int())
+    }
+
+    fn class_to_string(&self, class_int: u32) -> PyResult<String> {
+        let class = HandClass::from_int(class_int)
+            .ok_or_else(|| PyValueError::new_err(format!("Invalid rank class {}", class_int)))?;
+        Ok(EVALUATOR.class_to_string(class))
+    }
+
+    fn get_five_card_rank_percentage(&self, hand_rank: u32) -> f64 {
+        hand_rank as f64 / LookupTable::MAX_HIGH_CARD as f64
+    }
+}
+
+#[pyclass(name = "Deck")]
+struct PyDeck(Deck);
+
+#[pymethods]
+impl PyDeck {
+    #[new]
+    fn py_new() -> Self {
+        PyDeck(Deck::shuffled())
+    }
+
+    fn shuffle(&mut self) {
+        self.0.reset();
+        self.0.shuffle();
+    }
+
+    /// Returns a single card int when `n` is 1 and a list otherwise, like deuces.
+    #[pyo3(signature = (n=1))]
+    fn draw(&mut self, py: Python<'_>, n: usize) -> PyResult<PyObject> {
+        let cards: Vec<u32> = self.0.draw(n)?.iter().map(|c| c.0).collect();
+        if n == 1 {
+            Ok(cards[0].into_py(py))
+        } else {
+            Ok(cards.into_py(py))
+        }
+    }
+
+    #[getter]
+    fn cards(&self) -> Vec<u32> {
+        self.0.remaining().iter().map(|c| c.0).collect()
+    }
+
+    #[staticmethod]
+    #[allow(non_snake_case)]
+    fn GetFullDeck() -> Vec<u32> {
+        Deck::full_deck().iter().map(|c| c.0).collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[pyclass(name = "GameDealer")]
+struct PyGameDealer;
+
+#[pymethods]
+impl PyGameDealer {
+    #[new]
+    fn py_new() -> Self {
+        PyGameDealer
+    }
+
+    /// Deals a random hand to the river, returned as nested dicts. Passing
+    /// the `seed` and `button` of an earlier deal replays it.
+    #[pyo3(signature = (player_count, seed=None, button=0))]
+    fn deal<'py>(
+        &self,
+        py: Python<'py>,
+        player_count: usize,
+        seed: Option<u64>,
+        button: usize,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let shuffler = seed.map_or_else(SeededCardShuffler::from_entropy, SeededCardShuffler::new);
+        let deal = GameDealer::new(shuffler).with_button(button).deal(player_count)?;
+        let winners = deal.winners().to_vec();
+        let split_pot = deal.is_split_pot();
+        let board = PyDict::new_bound(py);
+        board.set_item("flop", deal.board.flop)?;
+        board.set_item("turn", deal.board.turn)?;
+        board.set_item("river", deal.board.river)?;
+        let hands = deal
+            .hands
+            .into_iter()
+            .map(|hand| {
+                let dict = PyDict::new_bound(py);
+                dict.set_item("cards", hand.cards)?;
+                dict.set_item("score", hand.score)?;
+                dict.set_item("description", hand.description)?;
+                dict.set_item("rank", hand.rank.value())?;
+                Ok(dict)
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        let result = PyDict::new_bound(py);
+        result.set_item("board", board)?;
+        result.set_item("hands", hands)?;
+        result.set_item("standings", deal.standings)?;
+        result.set_item("winners", winners)?;
+        result.set_item("split_pot", split_pot)?;
+        result.set_item("button", deal.button)?;
+        result.set_item("seed", deal.seed)?;
+        Ok(result)
+    }
+}
+
+#[pymodule]
+fn deuces_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyCard>()?;
+    m.add_class::<PyEvaluator>()?;
+    m.add_class::<PyDeck>()?;
+    m.add_class::<PyGameDealer>()?;
+    Ok(())
+}
@@ -0,0 +1,460 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::card::{Card, Rank, Suit};
+use crate::card_set::{parse_cards, CardSet};
+use crate::deck::Deck;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeParseError {
+    InvalidHand(String),
+    InvalidWeight(String),
+}
+
+impl fmt::Display for RangeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeParseError::InvalidHand(s) => write!(f, "invalid hand \"{}\"", s),
+            RangeParseError::InvalidWeight(s) => write!(f, "invalid weight \"{}\", expected a number in (0, 1]", s),
+        }
+    }
+}
+
+impl std::error::Error for RangeParseError {}
+
+/// Two distinct hole cards, the higher card first. Cards of equal rank are
+/// ordered by suit as in `Suit::ALL`, e.g. "AsAh".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Combo([Card; 2]);
+
+fn card_key(card: Card) -> usize {
+    card.rank() as usize * 4 + 3 - card.suit().index()
+}
+
+impl Combo {
+    /// Returns `None` if both cards are the same.
+    pub fn new(a: Card, b: Card) -> Option<Combo> {
+        match card_key(a).cmp(&card_key(b)) {
+            Ordering::Greater => Some(Combo([a, b])),
+            Ordering::Less => Some(Combo([b, a])),
+            Ordering::Equal => None,
+        }
+    }
+
+    pub fn cards(&self) -> [Card; 2] {
+        self.0
+    }
+
+    fn key(&self) -> (usize, usize) {
+        (card_key(self.0[0]), card_key(self.0[1]))
+    }
+}
+
+impl Ord for Combo {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl PartialOrd for Combo {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Combo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.0[0], self.0[1])
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Suitedness {
+    Suited,
+    Offsuit,
+    Any,
+}
+
+impl Suitedness {
+    fn suffix(self) -> &'static str {
+        match self {
+            Suitedness::Suited => "s",
+            Suitedness::Offsuit => "o",
+            Suitedness::Any => "",
+        }
+    }
+}
+
+/// A hand class such as "QQ", "AKs" or "T9".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Pair(Rank),
+    Unpaired(Rank, Rank, Suitedness),
+}
+
+impl Class {
+    fn parse(s: &str) -> Option<Class> {
+        let mut chars = s.chars();
+        let a = Rank::from_char(chars.next()?)?;
+        let b = Rank::from_char(chars.next()?)?;
+        let suitedness = match chars.next() {
+            None => Suitedness::Any,
+            Some('s' | 'S') => Suitedness::Suited,
+            Some('o' | 'O') => Suitedness::Offsuit,
+            Some(_) => return None,
+        };
+        if chars.next().is_some() {
+            return None;
+        }
+        match a.cmp(&b) {
+            Ordering::Equal if suitedness == Suitedness::Any => Some(Class::Pair(a)),
+            Ordering::Equal => None,
+            Ordering::Greater => Some(Class::Unpaired(a, b, suitedness)),
+            Ordering::Less => Some(Class::Unpaired(b, a, suitedness)),
+        }
+    }
+
+    fn combos(self) -> Vec<Combo> {
+        let mut combos = Vec::new();
+        let (high, low, suitedness) = match self {
+            Class::Pair(rank) => (rank, rank, Suitedness::Offsuit),
+            Class::Unpaired(high, low, suitedness) => (high, low, suitedness),
+        };
+        for a in Suit::ALL {
+            for b in Suit::ALL {
+                let suited = a == b;
+                let wanted = match suitedness {
+                    Suitedness::Suited => suited,
+                    Suitedness::Offsuit => !suited,
+                    Suitedness::Any => true,
+                };
+                // pairs are unordered, keep one of each
+                if wanted && (high != low || a.index() < b.index()) {
+                    combos.extend(Combo::new(Card::from_parts(high, a), Card::from_parts(low, b)));
+                }
+            }
+        }
+        combos
+    }
+}
+
+impl fmt::Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Class::Pair(rank) => write!(f, "{}{}", rank, rank),
+            Class::Unpaired(high, low, suitedness) => write!(f, "{}{}{}", high, low, suitedness.suffix()),
+        }
+    }
+}
+
+fn ranks_between(low: Rank, high: Rank) -> impl Iterator<Item = Rank> {
+    Rank::iter().filter(move |&rank| low <= rank && rank <= high)
+}
+
+/// Expands a token without weight into hand classes.
+fn parse_classes(token: &str) -> Option<Vec<Class>> {
+    if let Some(base) = token.strip_suffix('+') {
+        return match Class::parse(base)? {
+            Class::Pair(rank) => Some(ranks_between(rank, Rank::Ace).map(Class::Pair).collect()),
+            Class::Unpaired(high, low, suitedness) => Some(
+                ranks_between(low, high)
+                    .filter(|&kicker| kicker != high)
+                    .map(|kicker| Class::Unpaired(high, kicker, suitedness))
+                    .collect(),
+            ),
+        };
+    }
+    if let Some((from, to)) = token.split_once('-') {
+        return match (Class::parse(from)?, Class::parse(to)?) {
+            (Class::Pair(a), Class::Pair(b)) => Some(ranks_between(a.min(b), a.max(b)).map(Class::Pair).collect()),
+            (Class::Unpaired(high, a, suitedness), Class::Unpaired(other_high, b, other_suitedness))
+                if high == other_high && suitedness == other_suitedness =>
+            {
+                Some(
+                    ranks_between(a.min(b), a.max(b))
+                        .map(|kicker| Class::Unpaired(high, kicker, suitedness))
+                        .collect(),
+                )
+            }
+            _ => None,
+        };
+    }
+    Class::parse(token).map(|class| vec![class])
+}
+
+/// A weighted set of hole card combos, parsed from PokerStove style notation
+/// such as `"QQ+, AKs, AQo:0.5, 22-55"`. Besides hand classes, ranges may
+/// list exact combos like `"AhKh"` or be `"random"`. A combo listed twice
+/// keeps its last weight.
+///
+/// ```
+/// use deuces_rs::Range;
+///
+/// let range: Range = "QQ+, AKs, AQo:0.5, 22-55".parse().unwrap();
+/// assert_eq!(range.len(), 58);
+/// assert_eq!(range.to_string(), "QQ+, 55-22, AKs, AQo:0.5");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Range {
+    combos: BTreeMap<Combo, f64>,
+}
+
+impl Range {
+    pub fn new() -> Self {
+        Range::default()
+    }
+
+    /// All 1326 combos.
+    pub fn random() -> Self {
+        let deck = Deck::full_deck();
+        let mut range = Range::new();
+        for (i, &a) in deck.iter().enumerate() {
+            for &b in &deck[..i] {
+                range.insert(Combo::new(a, b).unwrap(), 1.0);
+            }
+        }
+        range
+    }
+
+    pub fn insert(&mut self, combo: Combo, weight: f64) {
+        self.combos.insert(combo, weight);
+    }
+
+    pub fn remove(&mut self, combo: Combo) -> Option<f64> {
+        self.combos.remove(&combo)
+    }
+
+    pub fn weight(&self, combo: Combo) -> Option<f64> {
+        self.combos.get(&combo).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    pub fn combos(&self) -> impl DoubleEndedIterator<Item = (Combo, f64)> + '_ {
+        self.combos.iter().map(|(&combo, &weight)| (combo, weight))
+    }
+
+    /// The combos that do not use any of the dead cards.
+    pub fn without_dead(&self, dead: &[Card]) -> Range {
+        let dead: CardSet = dead.iter().copied().collect();
+        let combos = self
+            .combos()
+            .filter(|(combo, _)| combo.cards().iter().all(|&card| !dead.contains(card)))
+            .collect();
+        Range { combos }
+    }
+
+    /// The weight shared by every combo of the class, if all are present.
+    fn class_weight(&self, class: Class) -> Option<f64> {
+        let mut weights = class.combos().into_iter().map(|combo| self.weight(combo));
+        let first = weights.next()??;
+        weights.all(|weight| weight == Some(first)).then_some(first)
+    }
+}
+
+fn weight_suffix(weight: f64) -> String {
+    if weight == 1.0 {
+        String::new()
+    } else {
+        format!(":{}", weight)
+    }
+}
+
+/// Appends runs of consecutive ranks sharing a weight, ranks going down from
+/// `top`, as "QQ+", "55-22" or "AKs" style tokens.
+fn push_runs(tokens: &mut Vec<String>, entries: &[(Rank, f64)], top: Rank, class: impl Fn(Rank) -> Class) {
+    let mut i = 0;
+    while i < entries.len() {
+        let (high, weight) = entries[i];
+        let mut j = i + 1;
+        while j < entries.len() && entries[j].1 == weight && entries[j].0 as u32 + 1 == entries[j - 1].0 as u32 {
+            j += 1;
+        }
+        let low = entries[j - 1].0;
+        let token = if j - i == 1 {
+            class(high).to_string()
+        } else if high == top {
+            format!("{}+", class(low))
+        } else {
+            format!("{}-{}", class(high), class(low))
+        };
+        tokens.push(token + &weight_suffix(weight));
+        i = j;
+    }
+}
+
+impl fmt::Display for Range {
+    /// Prints the canonical compact notation: pairs, then unpaired hands by
+    /// high card, then any combos not making up a whole class.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut tokens = Vec::new();
+        let mut covered = BTreeSet::new();
+        let mut cover = |class: Class| covered.extend(class.combos());
+
+        let pairs: Vec<_> = Rank::iter()
+            .rev()
+            .filter_map(|rank| Some((rank, self.class_weight(Class::Pair(rank))?)))
+            .collect();
+        pairs.iter().for_each(|&(rank, _)| cover(Class::Pair(rank)));
+        push_runs(&mut tokens, &pairs, Rank::Ace, Class::Pair);
+
+        for high in Rank::iter().rev() {
+            let mut any = Vec::new();
+            let mut suited = Vec::new();
+            let mut offsuit = Vec::new();
+            for low in Rank::iter().rev().filter(|&low| low < high) {
+                let class = |suitedness| Class::Unpaired(high, low, suitedness);
+                let s = self.class_weight(class(Suitedness::Suited));
+                let o = self.class_weight(class(Suitedness::Offsuit));
+                match (s, o) {
+                    (Some(s), Some(o)) if s == o => any.push((low, s)),
+                    _ => {
+                        suited.extend(s.map(|s| (low, s)));
+                        offsuit.extend(o.map(|o| (low, o)));
+                    }
+                }
+            }
+            let Some(top) = (high as u32).checked_sub(1).and_then(Rank::from_int) else {
+                continue;
+            };
+            for (entries, suitedness) in [
+                (any, Suitedness::Any),
+                (suited, Suitedness::Suited),
+                (offsuit, Suitedness::Offsuit),
+            ] {
+                let class = |low| Class::Unpaired(high, low, suitedness);
+                entries.iter().for_each(|&(low, _)| cover(class(low)));
+                push_runs(&mut tokens, &entries, top, class);
+            }
+        }
+
+        for (combo, weight) in self.combos().rev() {
+            if !covered.contains(&combo) {
+                tokens.push(combo.to_string() + &weight_suffix(weight));
+            }
+        }
+        f.write_str(&tokens.join(", "))
+    }
+}
+
+impl FromStr for Range {
+    type Err = RangeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut range = Range::new();
+        for token in s.split(',').map(str::trim).filter(|token| !token.is_empty()) {
+            let (hand, weight) = match token.split_once(':') {
+                Some((hand, weight)) => {
+                    let invalid = || RangeParseError::InvalidWeight(weight.to_string());
+                    let weight: f64 = weight.trim().parse().map_err(|_| invalid())?;
+                    if !(weight > 0.0 && weight <= 1.0) {
+                        return Err(invalid());
+                    }
+                    (hand.trim(), weight)
+                }
+                None => (token, 1.0),
+            };
+
+            if hand.eq_ignore_ascii_case("random") {
+                for (combo, _) in Range::random().combos() {
+                    range.insert(combo, weight);
+                }
+                continue;
+            }
+            let combo = match parse_cards(hand).as_deref() {
+                Ok(&[a, b]) => Combo::new(a, b),
+                _ => None,
+            };
+            if let Some(combo) = combo {
+                range.insert(combo, weight);
+                continue;
+            }
+            let classes = parse_classes(hand).ok_or_else(|| RangeParseError::InvalidHand(hand.to_string()))?;
+            for combo in classes.into_iter().flat_map(Class::combos) {
+                range.insert(combo, weight);
+            }
+        }
+        Ok(range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(s: &str) -> Range {
+        s.parse().unwrap()
+    }
+
+    fn combo(s: &str) -> Combo {
+        Combo::new(Card::new(&s[..2]).unwrap(), Card::new(&s[2..]).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let r = range("QQ+, AKs, AQo:0.5, 22-55");
+        assert_eq!(r.len(), 3 * 6 + 4 + 12 + 4 * 6);
+        assert_eq!(r.weight(combo("AhQs")), Some(0.5));
+        assert_eq!(r.weight(combo("KsAs")), Some(1.0));
+        assert_eq!(r.weight(combo("AhKs")), None);
+        assert_eq!(r.weight(combo("3c3d")), Some(1.0));
+        assert_eq!(r.weight(combo("6c6d")), None);
+
+        assert_eq!(range("A2s-A5s"), range("A5s-A2s"));
+        assert_eq!(range("A2s-A5s"), range("A2s, A3s, A4s, A5s"));
+        assert_eq!(range("ATs+"), range("ATs, AJs, AQs, AKs"));
+        assert_eq!(range("AK"), range("AKs, AKo"));
+        assert_eq!(range("76o").len(), 12);
+        assert_eq!(range("random").len(), 1326);
+        assert_eq!(range("AhKh, KhAh:0.25").weight(combo("AhKh")), Some(0.25));
+        assert_eq!(range("10h9h"), range("Th9h"));
+        assert_eq!(range("55-22"), range("22-55"));
+        assert!(range("").is_empty());
+    }
+
+    #[test]
+    fn test_canonical_notation() {
+        for (input, expected) in [
+            ("22-55, AKs, QQ+, AQo:0.5", "QQ+, 55-22, AKs, AQo:0.5"),
+            ("AKs, AKo", "AK"),
+            ("A2s-A5s, KK", "KK, A5s-A2s"),
+            ("ATs+, KQo:0.3, KJo:0.3", "ATs+, KJo+:0.3"),
+            ("K9o:0.3, KTo:0.3, 98s-96s", "KTo-K9o:0.3, 96s+"),
+            ("AhKh, AsKs:0.25", "AsKs:0.25, AhKh"),
+            ("AA, AsAh:0.5", "AsAh:0.5, AsAd, AsAc, AhAd, AhAc, AdAc"),
+        ] {
+            let r = range(input);
+            assert_eq!(r.to_string(), expected, "{}", input);
+            assert_eq!(range(expected), r);
+        }
+        let random = Range::random();
+        assert!(random.to_string().starts_with("22+, A2+, K2+"));
+        assert_eq!(range(&random.to_string()), random);
+    }
+
+    #[test]
+    fn test_dead_cards() {
+        let r = range("AK, QQ").without_dead(&[Card::new("Ah").unwrap(), Card::new("Qs").unwrap()]);
+        assert_eq!(r.len(), 12 + 3);
+        assert!(r.combos().all(|(combo, _)| !combo.to_string().contains("Ah")));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!("AKx".parse::<Range>(), Err(RangeParseError::InvalidHand("AKx".to_string())));
+        assert_eq!("AAs".parse::<Range>(), Err(RangeParseError::InvalidHand("AAs".to_string())));
+        assert_eq!("AKs-QJs".parse::<Range>(), Err(RangeParseError::InvalidHand("AKs-QJs".to_string())));
+        assert_eq!("AA-KQ".parse::<Range>(), Err(RangeParseError::InvalidHand("AA-KQ".to_string())));
+        assert_eq!("AhAh".parse::<Range>(), Err(RangeParseError::InvalidHand("AhAh".to_string())));
+        assert_eq!("AA:1.5".parse::<Range>(), Err(RangeParseError::InvalidWeight("1.5".to_string())));
+        assert_eq!("AA:x".parse::<Range>(), Err(RangeParseError::InvalidWeight("x".to_string())));
+    }
+}
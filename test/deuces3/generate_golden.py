@@ -0,0 +1,56 @@
+"""
+Writes the golden fixture replayed by tests/deuces3_golden.rs. Run from the
+repository root:
+
+    python3 -m test.deuces3.generate_golden
+
+Each line is a record kind followed by its fields:
+
+    card <card string> <card int>
+    flush <prime product> <rank>
+    unsuited <prime product> <rank>
+    hand <card strings...> <rank> <rank class>
+"""
+import contextlib
+import io
+import os
+import random
+
+from deuces3.card import Card
+from deuces3.deck import Deck
+from deuces3.evaluator import Evaluator
+
+FIXTURE = os.path.join(os.path.dirname(__file__), "..", "fixtures", "deuces3_golden.txt")
+SEED = 2023
+HANDS_PER_SIZE = 2000
+
+
+def main():
+    # the reference lookup table prints while it builds
+    with contextlib.redirect_stdout(io.StringIO()):
+        evaluator = Evaluator()
+
+    lines = []
+    for card_int in Deck.GetFullDeck():
+        lines.append("card %s %d" % (Card.int_to_str(card_int), card_int))
+    for prime, rank in sorted(evaluator.table.flush_lookup.items()):
+        lines.append("flush %d %d" % (prime, rank))
+    for prime, rank in sorted(evaluator.table.unsuited_lookup.items()):
+        lines.append("unsuited %d %d" % (prime, rank))
+
+    rng = random.Random(SEED)
+    deck = Deck.GetFullDeck()
+    for size in (5, 6, 7):
+        for _ in range(HANDS_PER_SIZE):
+            cards = rng.sample(deck, size)
+            rank = evaluator.evaluate(cards[:2], cards[2:])
+            rank_class = evaluator.get_rank_class(rank)
+            strs = " ".join(Card.int_to_str(c) for c in cards)
+            lines.append("hand %s %d %d" % (strs, rank, rank_class))
+
+    with open(FIXTURE, "w") as f:
+        f.write("\n".join(lines) + "\n")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,88 @@
+import contextlib
+import io
+import random
+
+import pytest
+
+deuces_rs = pytest.importorskip("deuces_rs")
+
+from deuces3.card import Card
+from deuces3.deck import Deck
+from deuces3.evaluator import Evaluator
+
+
+def reference_evaluator():
+    with contextlib.redirect_stdout(io.StringIO()):
+        return Evaluator()
+
+
+def test_card():
+    for card_int in Deck.GetFullDeck():
+        card_str = Card.int_to_str(card_int)
+        assert deuces_rs.Card.new(card_str) == card_int
+        assert deuces_rs.Card.int_to_str(card_int) == card_str
+        assert deuces_rs.Card.get_rank_int(card_int) == Card.get_rank_int(card_int)
+        assert deuces_rs.Card.get_suit_int(card_int) == Card.get_suit_int(card_int)
+        assert deuces_rs.Card.get_bitrank_int(card_int) == Card.get_bitrank_int(card_int)
+        assert deuces_rs.Card.get_prime(card_int) == Card.get_prime(card_int)
+
+
+def test_invalid_card():
+    with pytest.raises(ValueError):
+        deuces_rs.Card.new("Xx")
+    for card_int in (0, 0xFFFFFFFF):
+        with pytest.raises(ValueError):
+            deuces_rs.Card.int_to_str(card_int)
+        with pytest.raises(ValueError):
+            deuces_rs.Card.int_to_pretty_str(card_int)
+        with pytest.raises(ValueError):
+            deuces_rs.Card.print_pretty_card(card_int)
+        with pytest.raises(ValueError):
+            deuces_rs.Card.print_pretty_cards([Card.new("Ah"), card_int])
+
+
+def test_evaluator():
+    evaluator = reference_evaluator()
+    rs_evaluator = deuces_rs.Evaluator()
+    rng = random.Random(7)
+    for size in (5, 6, 7):
+        for _ in range(500):
+            cards = rng.sample(Deck.GetFullDeck(), size)
+            rank = evaluator.evaluate(cards[:2], cards[2:])
+            assert rs_evaluator.evaluate(cards[:2], cards[2:]) == rank
+            rank_class = evaluator.get_rank_class(rank)
+            assert rs_evaluator.get_rank_class(rank) == rank_class
+            assert rs_evaluator.class_to_string(rank_class) == evaluator.class_to_string(rank_class)
+
+
+def test_deck():
+    deck = deuces_rs.Deck()
+    assert sorted(deck.cards) == sorted(Deck.GetFullDeck())
+    assert isinstance(deck.draw(), int)
+    assert len(deck.draw(5)) == 5
+    assert len(deck.cards) == 46
+    deck.shuffle()
+    assert len(deck.cards) == 52
+
+
+def test_game_dealer():
+    deal = deuces_rs.GameDealer().deal(3)
+    assert len(deal["board"]["flop"]) == 3
+    assert len(deal["hands"]) == 3
+
+
+def test_game_dealer_replays_seed():
+    dealer = deuces_rs.GameDealer()
+    deal = dealer.deal(3)
+    assert dealer.deal(3, seed=deal["seed"]) == deal
+    moved = dealer.deal(3, seed=deal["seed"], button=1)
+    assert moved["button"] == 1
+    assert moved["hands"][1]["cards"] == deal["hands"][0]["cards"]
+
+
+def test_game_dealer_winners():
+    deal = deuces_rs.GameDealer().deal(4)
+    best = min(hand["rank"] for hand in deal["hands"])
+    winners = [i for i, hand in enumerate(deal["hands"]) if hand["rank"] == best]
+    assert deal["winners"] == winners == deal["standings"][0]
+    assert deal["split_pot"] == (len(winners) > 1)
@@ -0,0 +1,84 @@
+//! Replays the fixture written by `test/deuces3/generate_golden.py` from the
+//! bundled Python deuces3 reference.
+
+use deuces_rs::lookup::LOOKUP_TABLE;
+use deuces_rs::{Card, Evaluator, HandClass};
+
+const GOLDEN: &str = include_str!("../test/fixtures/deuces3_golden.txt");
+
+fn records(kind: &'static str) -> impl Iterator<Item = Vec<&'static str>> {
+    GOLDEN
+        .lines()
+        .map(|line| line.split(' ').collect::<Vec<_>>())
+        .filter(move |fields| fields[0] == kind)
+        .map(|fields| fields[1..].to_vec())
+}
+
+fn number(field: &str) -> u32 {
+    field.parse().unwrap()
+}
+
+/// The rank bits of a product of distinct rank primes, if it has no repeats.
+fn distinct_rankbits(mut product: u32) -> Option<u32> {
+    let mut bits = 0;
+    for (rank, &prime) in Card::PRIMES.iter().enumerate() {
+        if product.is_multiple_of(prime) {
+            product /= prime;
+            if product.is_multiple_of(prime) {
+                return None;
+            }
+            bits |= 1 << rank;
+        }
+    }
+    Some(bits)
+}
+
+#[test]
+fn test_cards() {
+    let cards: Vec<_> = records("card").collect();
+    assert_eq!(cards.len(), 52);
+    for fields in cards {
+        let card_int = number(fields[1]);
+        assert_eq!(Card::new(fields[0]).unwrap().0, card_int, "{}", fields[0]);
+        assert_eq!(Card::int_to_str(card_int), fields[0]);
+    }
+}
+
+#[test]
+fn test_flush_lookup() {
+    let flushes: Vec<_> = records("flush").collect();
+    assert_eq!(flushes.len(), 1287);
+    for fields in flushes {
+        let bits = distinct_rankbits(number(fields[0])).unwrap();
+        assert_eq!(LOOKUP_TABLE.flush_lookup[bits as usize] as u32, number(fields[1]));
+    }
+}
+
+#[test]
+fn test_unsuited_lookup() {
+    let unsuited: Vec<_> = records("unsuited").collect();
+    assert_eq!(unsuited.len(), 6175);
+    for fields in unsuited {
+        let product = number(fields[0]);
+        let rank = match distinct_rankbits(product) {
+            Some(bits) => LOOKUP_TABLE.unique_lookup[bits as usize] as u32,
+            None => LOOKUP_TABLE.multiples_rank(product).unwrap(),
+        };
+        assert_eq!(rank, number(fields[1]), "prime product {}", product);
+    }
+}
+
+#[test]
+fn test_hands() {
+    let evaluator = Evaluator::new();
+    let hands: Vec<_> = records("hand").collect();
+    assert_eq!(hands.len(), 6000);
+    for fields in hands {
+        let (cards, expected) = fields.split_at(fields.len() - 2);
+        let cards = Card::hand_to_binary(cards).unwrap();
+        let rank = evaluator.evaluate(&cards[..2], &cards[2..]).unwrap();
+        assert_eq!(rank.value(), number(expected[0]), "{:?}", fields);
+        let class = evaluator.get_rank_class(rank);
+        assert_eq!(class, HandClass::from_int(number(expected[1])).unwrap());
+    }
+}